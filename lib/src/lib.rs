@@ -1,4 +1,5 @@
 use alloy_sol_types::sol;
+use num_bigint::BigUint;
 
 sol! {
     /// The public values encoded as a struct that can be easily deserialized inside Solidity.
@@ -7,16 +8,61 @@ sol! {
         uint32 a;
         uint32 b;
     }
+
+    /// The public values of the exact mode, with `a` and `b` as big-endian unsigned integers of
+    /// any length.
+    struct ExactPublicValuesStruct {
+        uint32 n;
+        bytes a;
+        bytes b;
+    }
 }
 
 /// Compute the n'th fibonacci number (wrapping around on overflows), using normal Rust code.
+///
+/// Returns `(F(n), F(n + 1))` reduced mod 2^32, with `F(0) = 0` and `F(1) = 1`. These wrapped values
+/// are what the program commits to, and what the host checks proofs against.
+///
+/// Uses fast doubling, so it takes O(log n) steps:
+///
+/// ```text
+/// F(2k)     = F(k) * (2 * F(k + 1) - F(k))
+/// F(2k + 1) = F(k)^2 + F(k + 1)^2
+/// ```
+///
+/// The identities only add, subtract and multiply, so they hold mod 2^32 as well.
 pub fn fibonacci(n: u32) -> (u32, u32) {
+    // (F(k), F(k + 1)) for k the bits of n seen so far.
     let mut a = 0u32;
     let mut b = 1u32;
-    for _ in 0..n {
-        let c = a.wrapping_add(b);
-        a = b;
-        b = c;
+    for bit in (0..u32::BITS - n.leading_zeros()).rev() {
+        let c = a.wrapping_mul(b.wrapping_mul(2).wrapping_sub(a));
+        let d = a.wrapping_mul(a).wrapping_add(b.wrapping_mul(b));
+        (a, b) = if (n >> bit) & 1 == 1 {
+            (d, c.wrapping_add(d))
+        } else {
+            (c, d)
+        };
+    }
+    (a, b)
+}
+
+/// Compute the n'th fibonacci number exactly, using arbitrary-precision integers.
+///
+/// Returns `(F(n), F(n + 1))` by the same fast doubling as [`fibonacci`]. `F(n)` has about
+/// `0.7 * n` bits, so this is only practical for `n` up to the millions.
+pub fn fibonacci_exact(n: u32) -> (BigUint, BigUint) {
+    let mut a = BigUint::from(0u32);
+    let mut b = BigUint::from(1u32);
+    for bit in (0..u32::BITS - n.leading_zeros()).rev() {
+        let c = &a * (&b * 2u32 - &a);
+        let d = &a * &a + &b * &b;
+        (a, b) = if (n >> bit) & 1 == 1 {
+            let e = &c + &d;
+            (d, e)
+        } else {
+            (c, d)
+        };
     }
     (a, b)
 }
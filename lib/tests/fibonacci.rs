@@ -0,0 +1,143 @@
+//! Tests for `fibonacci` against the original iterative loop and a `num_bigint` reference, reduced
+//! mod 2^32, and for `fibonacci_exact` against the unreduced reference.
+
+use fibonacci_lib::{fibonacci, fibonacci_exact};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+
+/// `(F(n), F(n + 1))` by the O(n) loop `fibonacci` was originally written as.
+fn iterative_fibonacci(n: u32) -> (u32, u32) {
+    let mut a = 0u32;
+    let mut b = 1u32;
+    for _ in 0..n {
+        let c = a.wrapping_add(b);
+        a = b;
+        b = c;
+    }
+    (a, b)
+}
+
+/// `(F(n), F(n + 1))` computed exactly by iteration.
+fn unreduced_reference(n: u32) -> (BigUint, BigUint) {
+    let mut a = BigUint::from(0u32);
+    let mut b = BigUint::from(1u32);
+    for _ in 0..n {
+        let c = &a + &b;
+        a = b;
+        b = c;
+    }
+    (a, b)
+}
+
+/// `(F(n), F(n + 1))` computed exactly, then reduced mod 2^32.
+fn exact_reference(n: u32) -> (u32, u32) {
+    let (a, b) = unreduced_reference(n);
+    (reduce(&a), reduce(&b))
+}
+
+/// `(F(n), F(n + 1))` mod 2^32 by fast doubling, reducing after every step so large `n` stay cheap:
+/// `F(2k) = F(k) * (2 * F(k + 1) - F(k))` and `F(2k + 1) = F(k)^2 + F(k + 1)^2`.
+fn modular_reference(n: u32) -> (u32, u32) {
+    let modulus = BigUint::from(1u64 << 32);
+    let mut a = BigUint::from(0u32);
+    let mut b = BigUint::from(1u32);
+    for bit in (0..u32::BITS).rev() {
+        let two_b_minus_a = (&b * 2u32 + &modulus - &a) % &modulus;
+        let c = (&a * two_b_minus_a) % &modulus;
+        let d = (&a * &a + &b * &b) % &modulus;
+        (a, b) = if n >> bit & 1 == 1 {
+            let e = (&c + &d) % &modulus;
+            (d, e)
+        } else {
+            (c, d)
+        };
+    }
+    (reduce(&a), reduce(&b))
+}
+
+fn reduce(value: &BigUint) -> u32 {
+    value.iter_u32_digits().next().unwrap_or(0)
+}
+
+#[test]
+fn fibonacci_of_zero() {
+    assert_eq!(fibonacci(0), (0, 1));
+}
+
+#[test]
+fn fibonacci_of_one() {
+    assert_eq!(fibonacci(1), (1, 1));
+}
+
+#[test]
+fn fibonacci_wraps_on_overflow() {
+    // F(48) = 4807526976 is the first Fibonacci number that does not fit in a u32.
+    assert_eq!(fibonacci(47), (2971215073, 4807526976u64 as u32));
+    assert_eq!(exact_reference(47), fibonacci(47));
+}
+
+#[test]
+fn modular_reference_matches_exact_reference() {
+    for n in 0..1000 {
+        assert_eq!(modular_reference(n), exact_reference(n), "n = {n}");
+    }
+}
+
+#[test]
+fn fibonacci_of_u32_max() {
+    assert_eq!(fibonacci(u32::MAX), modular_reference(u32::MAX));
+}
+
+#[test]
+fn matches_iterative_fibonacci_up_to_100_000() {
+    let (mut a, mut b) = (0u32, 1u32);
+    for n in 0..100_000 {
+        assert_eq!(fibonacci(n), (a, b), "n = {n}");
+        (a, b) = (b, a.wrapping_add(b));
+    }
+}
+
+/// The loop runs `u32::MAX` times, which takes over a second in release builds and far longer in
+/// debug builds, so this only runs with `cargo test --release -- --ignored`.
+#[test]
+#[ignore]
+fn iterative_fibonacci_of_u32_max() {
+    assert_eq!(iterative_fibonacci(u32::MAX), fibonacci(u32::MAX));
+}
+
+#[test]
+fn fibonacci_exact_of_100() {
+    let (a, b) = fibonacci_exact(100);
+    assert_eq!(a.to_string(), "354224848179261915075");
+    assert_eq!(b.to_string(), "573147844013817084101");
+}
+
+#[test]
+fn fibonacci_exact_matches_unreduced_reference_up_to_2_000() {
+    for n in 0..2_000 {
+        assert_eq!(fibonacci_exact(n), unreduced_reference(n), "n = {n}");
+    }
+}
+
+proptest! {
+    #[test]
+    fn matches_exact_reference(n in 0u32..5_000) {
+        prop_assert_eq!(fibonacci(n), exact_reference(n));
+    }
+
+    #[test]
+    fn matches_modular_reference(n in any::<u32>()) {
+        prop_assert_eq!(fibonacci(n), modular_reference(n));
+    }
+
+    #[test]
+    fn matches_iterative_fibonacci(n in 0u32..1_000_000) {
+        prop_assert_eq!(fibonacci(n), iterative_fibonacci(n));
+    }
+
+    #[test]
+    fn fibonacci_exact_reduces_to_fibonacci(n in 0u32..100_000) {
+        let (a, b) = fibonacci_exact(n);
+        prop_assert_eq!((reduce(&a), reduce(&b)), fibonacci(n));
+    }
+}
@@ -1,5 +1,8 @@
 //! A simple program that takes a number `n` as input, and writes the `n-1`th and `n`th fibonacci
 //! number as an output.
+//!
+//! A second input selects the exact mode, which commits the numbers as arbitrary-precision integers
+//! instead of mod 2^32.
 
 // These two lines are necessary for the program to properly compile.
 //
@@ -9,7 +12,7 @@
 sp1_zkvm::entrypoint!(main);
 
 use alloy_sol_types::SolType;
-use fibonacci_lib::{fibonacci, PublicValuesStruct};
+use fibonacci_lib::{fibonacci, fibonacci_exact, ExactPublicValuesStruct, PublicValuesStruct};
 
 pub fn main() {
     // Read an input to the program.
@@ -17,12 +20,22 @@ pub fn main() {
     // Behind the scenes, this compiles down to a custom system call which handles reading inputs
     // from the prover.
     let n = sp1_zkvm::io::read::<u32>();
+    let exact = sp1_zkvm::io::read::<bool>();
 
-    // Compute the n'th fibonacci number using a function from the workspace lib crate.
-    let (a, b) = fibonacci(n);
-
-    // Encode the public values of the program.
-    let bytes = PublicValuesStruct::abi_encode(&PublicValuesStruct { n, a, b });
+    // Compute the n'th fibonacci number using a function from the workspace lib crate, and encode
+    // the public values of the program. It takes O(log n) steps, so the cycle count of the
+    // wrapping mode barely grows with n.
+    let bytes = if exact {
+        let (a, b) = fibonacci_exact(n);
+        ExactPublicValuesStruct::abi_encode(&ExactPublicValuesStruct {
+            n,
+            a: a.to_bytes_be().into(),
+            b: b.to_bytes_be().into(),
+        })
+    } else {
+        let (a, b) = fibonacci(n);
+        PublicValuesStruct::abi_encode(&PublicValuesStruct { n, a, b })
+    };
 
     // Commit to the public values of the program. The final proof will have a commitment to all the
     // bytes that were committed to.
@@ -9,44 +9,153 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release --bin evm -- --system groth16
 //! ```
+//! or, to keep fixtures for several inputs side by side:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --n 10 --fixture-dir fixtures \
+//!     --fixture-name "{system}-{n}-{timestamp}.json" --no-overwrite
+//! ```
+//! or, to generate a fixture for each of several inputs with a single setup:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --n-range 0..50 \
+//!     --fixture-name "{system}-{n}-fixture.json"
+//! ```
+//! or, to generate both PLONK and Groth16 fixtures from the same compressed proof:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --system all
+//! ```
+//! or, to test the fixture pipeline with placeholder proofs from the mock prover:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --system all --mock
+//! ```
+//! or, to generate fixtures committing to the exact Fibonacci numbers instead of their values
+//! mod 2^32:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --n 100 --exact
+//! ```
 
 use alloy_sol_types::SolType;
 use clap::{Parser, ValueEnum};
 use fibonacci_lib::PublicValuesStruct;
+use fibonacci_verifier::{ExactFibonacciValues, ProofSystem, SP1FibonacciProofFixture};
 use serde::{Deserialize, Serialize};
-use sp1_sdk::{HashableKey, ProverClient, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
-use std::path::PathBuf;
+use sp1_sdk::{
+    install::try_install_circuit_artifacts,
+    provers::{ProofOpts, ProverType},
+    HashableKey, ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey, SP1Stdin,
+    SP1VerifyingKey,
+};
+use std::{
+    collections::HashSet,
+    fs::OpenOptions,
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const FIBONACCI_ELF: &[u8] = include_bytes!("../../../elf/riscv32im-succinct-zkvm-elf");
 
+/// The file in the fixture directory listing the fixtures of the last run.
+const INDEX_FILE: &str = "index.json";
+
 /// The arguments for the EVM command.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct EVMArgs {
-    #[clap(long, default_value = "20")]
-    n: u32,
+    /// The inputs to generate fixtures for, separated by commas.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        default_value = "20",
+        conflicts_with = "n_range"
+    )]
+    n: Vec<u32>,
+
+    /// A range of inputs to generate fixtures for, e.g. `0..50`. The end is exclusive.
+    #[clap(long, value_parser = parse_n_range)]
+    n_range: Option<Range<u32>>,
+
     #[clap(long, value_enum, default_value = "plonk")]
-    system: ProofSystem,
+    system: SystemArg,
+
+    /// The directory to write fixtures, and the index listing them, to. Defaults to
+    /// `contracts/src/fixtures`.
+    #[clap(long)]
+    fixture_dir: Option<PathBuf>,
+
+    /// The fixture file name. `{system}`, `{n}` and `{timestamp}` (seconds since the Unix epoch)
+    /// are replaced with their values.
+    #[clap(long, default_value = "{system}-fixture.json")]
+    fixture_name: String,
+
+    /// Fail instead of overwriting an existing fixture or `index.json`.
+    #[clap(long)]
+    no_overwrite: bool,
+
+    /// Use the mock prover, which executes the program but returns placeholder proofs. The
+    /// fixtures have real public values and vkey, and are marked with `"mock": true`.
+    #[clap(long)]
+    mock: bool,
+
+    /// Compute the exact Fibonacci numbers instead of their values mod 2^32. The fixtures hold
+    /// them in decimal under `exact`.
+    #[clap(long)]
+    exact: bool,
 }
 
-/// Enum representing the available proof systems
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
-enum ProofSystem {
+/// The proof systems to generate fixtures for.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+enum SystemArg {
     Plonk,
     Groth16,
+    /// Both PLONK and Groth16, wrapped from the same compressed proof.
+    All,
+}
+
+impl SystemArg {
+    /// The proof systems selected.
+    fn systems(self) -> Vec<ProofSystem> {
+        match self {
+            SystemArg::Plonk => vec![ProofSystem::Plonk],
+            SystemArg::Groth16 => vec![ProofSystem::Groth16],
+            SystemArg::All => vec![ProofSystem::Plonk, ProofSystem::Groth16],
+        }
+    }
+}
+
+impl EVMArgs {
+    /// The inputs to generate fixtures for.
+    fn inputs(&self) -> Vec<u32> {
+        match &self.n_range {
+            Some(range) => range.clone().collect(),
+            None => self.n.clone(),
+        }
+    }
+
+    /// The directory fixtures are written to.
+    fn fixture_dir(&self) -> PathBuf {
+        self.fixture_dir.clone().unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures")
+        })
+    }
+
+    /// The file name of the fixture for input `n` in the given proof system.
+    fn fixture_name(&self, system: ProofSystem, n: u32, timestamp: u64) -> String {
+        self.fixture_name
+            .replace("{system}", &format!("{:?}", system).to_lowercase())
+            .replace("{n}", &n.to_string())
+            .replace("{timestamp}", &timestamp.to_string())
+    }
 }
 
-/// A fixture that can be used to test the verification of SP1 zkVM proofs inside Solidity.
+/// An entry of the index written next to a batch of fixtures.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct SP1FibonacciProofFixture {
-    a: u32,
-    b: u32,
+struct FixtureIndexEntry {
     n: u32,
-    vkey: String,
-    public_values: String,
-    proof: String,
+    system: ProofSystem,
+    file: String,
 }
 
 fn main() {
@@ -56,38 +165,239 @@ fn main() {
     // Parse the command line arguments.
     let args = EVMArgs::parse();
 
+    let inputs = args.inputs();
+    if inputs.is_empty() {
+        eprintln!("Error: no inputs to generate fixtures for");
+        std::process::exit(1);
+    }
+    let systems = args.system.systems();
+
+    // Resolve the fixture paths up front so clashing or existing fixtures are reported before
+    // proving.
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs();
+    let fixture_dir = args.fixture_dir();
+    let fixture_name = |system, n| args.fixture_name(system, n, timestamp);
+    let fixture_names: Vec<String> = inputs
+        .iter()
+        .flat_map(|&n| systems.iter().map(move |&system| fixture_name(system, n)))
+        .collect();
+    let unique_names: HashSet<_> = fixture_names.iter().map(String::as_str).collect();
+    if unique_names.len() != fixture_names.len() || unique_names.contains(INDEX_FILE) {
+        eprintln!(
+            "Error: --fixture-name {:?} gives several fixtures the same file, or the name of \
+             the {INDEX_FILE}; include {{system}} and {{n}}",
+            args.fixture_name
+        );
+        std::process::exit(1);
+    }
+    if args.no_overwrite {
+        for name in fixture_names.iter().map(String::as_str).chain([INDEX_FILE]) {
+            let fixture_path = fixture_dir.join(name);
+            if fixture_path.exists() {
+                eprintln!("Error: fixture {} already exists", fixture_path.display());
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Setup the prover client.
-    let client = ProverClient::new();
+    let client = if args.mock {
+        ProverClient::mock()
+    } else {
+        ProverClient::new()
+    };
+    let mock = client.prover.id() == ProverType::Mock;
 
-    // Setup the program.
+    // Setup the program once for all inputs.
     let (pk, vk) = client.setup(FIBONACCI_ELF);
 
-    // Setup the inputs.
-    let mut stdin = SP1Stdin::new();
-    stdin.write(&args.n);
-
-    println!("n: {}", args.n);
     println!("Proof System: {:?}", args.system);
 
-    // Generate the proof based on the selected proof system.
-    let proof = match args.system {
-        ProofSystem::Plonk => client.prove(&pk, stdin).plonk().run(),
-        ProofSystem::Groth16 => client.prove(&pk, stdin).groth16().run(),
+    let mut index = Vec::with_capacity(fixture_names.len());
+    for &n in &inputs {
+        // Setup the inputs.
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&n);
+        stdin.write(&args.exact);
+
+        println!("n: {}", n);
+
+        // Generate the proof based on the selected proof system.
+        let proofs = if args.system == SystemArg::All && !mock {
+            let (shared, proofs) = prove_all_systems(&client, &pk, stdin);
+            print_summary(n, shared, &proofs);
+            proofs
+        } else {
+            // Mock proofs are cheap, and have no compressed proof to share between systems.
+            systems
+                .iter()
+                .map(|&system| {
+                    let start = Instant::now();
+                    let proof = match system {
+                        ProofSystem::Plonk => client.prove(&pk, stdin.clone()).plonk().run(),
+                        ProofSystem::Groth16 => client.prove(&pk, stdin.clone()).groth16().run(),
+                    }
+                    .expect("failed to generate proof");
+                    (system, proof, start.elapsed())
+                })
+                .collect()
+        };
+
+        for (system, proof, _) in &proofs {
+            let name = fixture_name(*system, n);
+            create_proof_fixture(
+                proof,
+                &vk,
+                mock,
+                args.exact,
+                &fixture_dir.join(&name),
+                args.no_overwrite,
+            );
+            index.push(FixtureIndexEntry {
+                n,
+                system: *system,
+                file: name,
+            });
+        }
+    }
+
+    write_fixture_index(&fixture_dir, &index, args.no_overwrite);
+}
+
+/// Prove the program once up to the compressed proof, then wrap that proof into both PLONK and
+/// Groth16.
+///
+/// Returns the time spent on the shared proving, and each proof with the time spent wrapping it.
+/// This always wraps locally, as the prover network only returns the final proof.
+fn prove_all_systems(
+    client: &ProverClient,
+    pk: &SP1ProvingKey,
+    stdin: SP1Stdin,
+) -> (
+    Duration,
+    Vec<(ProofSystem, SP1ProofWithPublicValues, Duration)>,
+) {
+    let start = Instant::now();
+    let compressed = client
+        .prove(pk, stdin)
+        .compressed()
+        .run()
+        .expect("failed to generate compressed proof");
+    let SP1Proof::Compressed(reduce_proof) = compressed.proof else {
+        unreachable!("the prover returned a {:?} proof", compressed.proof);
+    };
+
+    // Shrink and wrap the compressed proof into a BN254-friendly one, as the SDK does for each
+    // EVM proof.
+    let prover = client.prover.sp1_prover();
+    let opts = ProofOpts::default().sp1_prover_opts;
+    let shrink_proof = prover
+        .shrink(*reduce_proof, opts)
+        .expect("failed to shrink proof");
+    let outer_proof = prover
+        .wrap_bn254(shrink_proof, opts)
+        .expect("failed to wrap proof");
+    let shared = start.elapsed();
+
+    let proofs = [ProofSystem::Plonk, ProofSystem::Groth16]
+        .into_iter()
+        .map(|system| {
+            let start = Instant::now();
+            let proof = match system {
+                ProofSystem::Plonk => {
+                    let artifacts = if sp1_prover::build::sp1_dev_mode() {
+                        sp1_prover::build::try_build_plonk_bn254_artifacts_dev(
+                            &outer_proof.vk,
+                            &outer_proof.proof,
+                        )
+                    } else {
+                        try_install_circuit_artifacts()
+                    };
+                    SP1Proof::Plonk(prover.wrap_plonk_bn254(outer_proof.clone(), &artifacts))
+                }
+                ProofSystem::Groth16 => {
+                    let artifacts = if sp1_prover::build::sp1_dev_mode() {
+                        sp1_prover::build::try_build_groth16_bn254_artifacts_dev(
+                            &outer_proof.vk,
+                            &outer_proof.proof,
+                        )
+                    } else {
+                        try_install_circuit_artifacts()
+                    };
+                    SP1Proof::Groth16(prover.wrap_groth16_bn254(outer_proof.clone(), &artifacts))
+                }
+            };
+            let proof = SP1ProofWithPublicValues {
+                proof,
+                stdin: compressed.stdin.clone(),
+                public_values: compressed.public_values.clone(),
+                sp1_version: compressed.sp1_version.clone(),
+            };
+            (system, proof, start.elapsed())
+        })
+        .collect();
+
+    (shared, proofs)
+}
+
+/// Compare the sizes and generation times of the proofs of one input.
+fn print_summary(
+    n: u32,
+    shared: Duration,
+    proofs: &[(ProofSystem, SP1ProofWithPublicValues, Duration)],
+) {
+    println!("Summary for n = {n}:");
+    println!("  shared core and compressed proving: {:.1?}", shared);
+    for (system, proof, elapsed) in proofs {
+        println!(
+            "  {:?}: {} proof bytes, wrapped in {:.1?}",
+            system,
+            proof.bytes().len(),
+            elapsed
+        );
     }
-    .expect("failed to generate proof");
+}
 
-    create_proof_fixture(&proof, &vk, args.system);
+/// Report public values that do not decode in the requested mode, and exit.
+fn exit_on_public_values(err: impl std::fmt::Display) -> ! {
+    eprintln!("Error: failed to decode the proof's public values: {err}");
+    std::process::exit(1);
 }
 
-/// Create a fixture for the given proof.
+/// Create a fixture for the given proof at `fixture_path`, marked as a mock fixture if the proof
+/// is from the mock prover, and with the exact values if it is of the exact mode.
 fn create_proof_fixture(
     proof: &SP1ProofWithPublicValues,
     vk: &SP1VerifyingKey,
-    system: ProofSystem,
+    mock: bool,
+    exact: bool,
+    fixture_path: &Path,
+    no_overwrite: bool,
 ) {
     // Deserialize the public values.
     let bytes = proof.public_values.as_slice();
-    let PublicValuesStruct { n, a, b } = PublicValuesStruct::abi_decode(bytes, false).unwrap();
+    let (PublicValuesStruct { n, a, b }, exact) = if exact {
+        let verified = fibonacci_verifier::check_exact_fibonacci_public_values(bytes)
+            .unwrap_or_else(|err| exit_on_public_values(err));
+        let wrapped = verified.wrapped();
+        let values = PublicValuesStruct {
+            n: wrapped.n,
+            a: wrapped.a,
+            b: wrapped.b,
+        };
+        let exact = ExactFibonacciValues {
+            a: verified.a.to_string(),
+            b: verified.b.to_string(),
+        };
+        (values, Some(exact))
+    } else {
+        let values = PublicValuesStruct::abi_decode(bytes, false)
+            .unwrap_or_else(|err| exit_on_public_values(err));
+        (values, None)
+    };
 
     // Create the testing fixture so we can test things end-to-end.
     let fixture = SP1FibonacciProofFixture {
@@ -97,6 +407,8 @@ fn create_proof_fixture(
         vkey: vk.bytes32().to_string(),
         public_values: format!("0x{}", hex::encode(bytes)),
         proof: format!("0x{}", hex::encode(proof.bytes())),
+        exact,
+        mock,
     };
 
     // The verification key is used to verify that the proof corresponds to the execution of the
@@ -114,13 +426,48 @@ fn create_proof_fixture(
     // The proof proves to the verifier that the program was executed with some inputs that led to
     // the give public values.
     println!("Proof Bytes: {}", fixture.proof);
+    if mock {
+        println!("The proof is a placeholder from the mock prover and will not verify.");
+    }
 
     // Save the fixture to a file.
-    let fixture_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures");
-    std::fs::create_dir_all(&fixture_path).expect("failed to create fixture path");
-    std::fs::write(
-        fixture_path.join(format!("{:?}-fixture.json", system).to_lowercase()),
-        serde_json::to_string_pretty(&fixture).unwrap(),
-    )
-    .expect("failed to write fixture");
+    write_fixture_file(fixture_path, &fixture, no_overwrite);
+    println!("Saved fixture to {}", fixture_path.display());
+}
+
+/// Write a fixture file as JSON, failing instead of replacing an existing file if `no_overwrite`
+/// is set.
+fn write_fixture_file(path: &Path, contents: &impl Serialize, no_overwrite: bool) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).expect("failed to create fixture directory");
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(!no_overwrite)
+        .create_new(no_overwrite)
+        .open(path)
+        .unwrap_or_else(|err| {
+            eprintln!("Error: failed to create fixture {}: {err}", path.display());
+            std::process::exit(1);
+        });
+    file.write_all(serde_json::to_string_pretty(contents).unwrap().as_bytes())
+        .expect("failed to write fixture");
+}
+
+/// List the fixtures generated by this run in `index.json` in the fixture directory.
+fn write_fixture_index(fixture_dir: &Path, index: &[FixtureIndexEntry], no_overwrite: bool) {
+    let index_path = fixture_dir.join(INDEX_FILE);
+    write_fixture_file(&index_path, &index, no_overwrite);
+    println!("Saved fixture index to {}", index_path.display());
+}
+
+/// Parse an exclusive range of inputs, e.g. `0..50`.
+fn parse_n_range(range: &str) -> Result<Range<u32>, String> {
+    let (start, end) = range
+        .split_once("..")
+        .ok_or_else(|| format!("expected a range like 0..50, got {range:?}"))?;
+    let start = start.parse::<u32>().map_err(|err| err.to_string())?;
+    let end = end.parse::<u32>().map_err(|err| err.to_string())?;
+    Ok(start..end)
 }
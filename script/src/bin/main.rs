@@ -9,14 +9,24 @@
 //! ```shell
 //! RUST_LOG=info cargo run --release -- --prove
 //! ```
+//! or, to verify a previously saved Groth16 or PLONK proof without proving:
+//! ```shell
+//! RUST_LOG=info cargo run --release -- verify --proof proof.bin
+//! ```
+//! or, to run the proving flow with a placeholder proof from the mock prover:
+//! ```shell
+//! RUST_LOG=info cargo run --release -- --prove --mock
+//! ```
+//! Add `--exact` to any of these to prove the exact Fibonacci numbers instead of their values
+//! mod 2^32.
 
-use alloy_sol_types::SolType;
-use clap::Parser;
-use fibonacci_lib::PublicValuesStruct;
-use gnark_bn254_verifier::Fr;
-use num_bigint::BigUint;
-use sp1_sdk::{ProverClient, SP1Proof, SP1Stdin};
-use std::str::FromStr;
+use clap::{Parser, Subcommand};
+use fibonacci_verifier::{
+    ProofSystem, SP1FibonacciProofFixture, VerificationError, VerifiedExactFibonacci,
+};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, ProverClient, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
+use std::path::{Path, PathBuf};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const FIBONACCI_ELF: &[u8] = include_bytes!("../../../elf/riscv32im-succinct-zkvm-elf");
@@ -25,6 +35,9 @@ pub const FIBONACCI_ELF: &[u8] = include_bytes!("../../../elf/riscv32im-succinct
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     #[clap(long)]
     execute: bool,
 
@@ -33,6 +46,98 @@ struct Args {
 
     #[clap(long, default_value = "20")]
     n: u32,
+
+    /// The proof system to prove with, Groth16 by default. With `verify` and `verify-fixture`, the
+    /// proof system the proof must be of, by default whichever it is.
+    #[clap(long, value_enum, global = true)]
+    system: Option<ProofSystem>,
+
+    /// The SP1 circuit's Groth16 verifying key. Defaults to the key embedded with the
+    /// `embedded-vk` feature, or else the key installed under `~/.sp1/circuits`. With
+    /// `embedded-vk`, `SP1_GROTH16_VK` is only read at build time and cannot replace the key.
+    #[cfg_attr(
+        not(feature = "embedded-vk"),
+        clap(long, global = true, env = "SP1_GROTH16_VK")
+    )]
+    #[cfg_attr(feature = "embedded-vk", clap(long, global = true))]
+    groth16_vk: Option<PathBuf>,
+
+    /// The SP1 circuit's PLONK verifying key. Defaults to the key installed under
+    /// `~/.sp1/circuits`.
+    #[clap(long, global = true, env = "SP1_PLONK_VK")]
+    plonk_vk: Option<PathBuf>,
+
+    /// Save the generated proof to this path, with a JSON summary next to it.
+    #[clap(long)]
+    out: Option<PathBuf>,
+
+    /// Use the mock prover, which executes the program but returns a placeholder proof. Only the
+    /// proof's public inputs and values are checked.
+    #[clap(long)]
+    mock: bool,
+
+    /// Compute the exact Fibonacci numbers instead of their values mod 2^32. With `verify`, the
+    /// proof is expected to be of this mode.
+    #[clap(long, global = true)]
+    exact: bool,
+}
+
+impl Args {
+    /// The configured verifying key for the given proof system, if any.
+    fn vk_path(&self, system: ProofSystem) -> Option<&Path> {
+        match system {
+            ProofSystem::Plonk => self.plonk_vk.as_deref(),
+            ProofSystem::Groth16 => self.groth16_vk.as_deref(),
+        }
+    }
+}
+
+/// Commands that work on existing proofs instead of running the program.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Verify a saved Groth16 or PLONK proof locally.
+    Verify {
+        /// The serialized `SP1ProofWithPublicValues` to verify.
+        #[clap(long)]
+        proof: PathBuf,
+
+        /// The SP1 circuit's verifying key. Defaults to `--groth16-vk` or `--plonk-vk`, depending
+        /// on the proof system of the proof.
+        #[clap(long)]
+        vk: Option<PathBuf>,
+
+        /// The expected program vkey hash. Defaults to the vkey of the Fibonacci program.
+        #[clap(long)]
+        program_vkey: Option<String>,
+    },
+
+    /// Verify a Solidity test fixture written by the `evm` binary locally.
+    VerifyFixture {
+        /// The fixture JSON file, e.g. `groth16-fixture.json`. Its proof system is the one of
+        /// `--system`, or else the system whose verifying key has the selector the fixture's proof
+        /// starts with.
+        path: PathBuf,
+
+        /// The SP1 circuit's verifying key. Defaults to `--groth16-vk` or `--plonk-vk`, depending
+        /// on the proof system.
+        #[clap(long)]
+        vk: Option<PathBuf>,
+
+        /// The expected program vkey hash. Defaults to the vkey of the Fibonacci program.
+        #[clap(long)]
+        program_vkey: Option<String>,
+    },
+}
+
+/// A summary of a saved proof, written next to it so it can be inspected without bincode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedProofSummary {
+    vkey: String,
+    public_values: String,
+    proof: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    mock: bool,
 }
 
 fn main() {
@@ -42,17 +147,42 @@ fn main() {
     // Parse the command line arguments.
     let args = Args::parse();
 
+    match &args.command {
+        Some(Command::Verify {
+            proof,
+            vk,
+            program_vkey,
+        }) => {
+            verify_saved_proof(&args, proof, vk.as_deref(), program_vkey.as_deref());
+            return;
+        }
+        Some(Command::VerifyFixture {
+            path,
+            vk,
+            program_vkey,
+        }) => {
+            verify_fixture_file(&args, path, vk.as_deref(), program_vkey.as_deref());
+            return;
+        }
+        None => {}
+    }
+
     if args.execute == args.prove {
         eprintln!("Error: You must specify either --execute or --prove");
         std::process::exit(1);
     }
 
     // Setup the prover client.
-    let client = ProverClient::new();
+    let client = if args.mock {
+        ProverClient::mock()
+    } else {
+        ProverClient::new()
+    };
 
     // Setup the inputs.
     let mut stdin = SP1Stdin::new();
     stdin.write(&args.n);
+    stdin.write(&args.exact);
 
     println!("n: {}", args.n);
 
@@ -61,60 +191,259 @@ fn main() {
         let (output, report) = client.execute(FIBONACCI_ELF, stdin).run().unwrap();
         println!("Program executed successfully.");
 
-        // Read the output.
-        let decoded = PublicValuesStruct::abi_decode(output.as_slice(), true).unwrap();
-        let PublicValuesStruct { n, a, b } = decoded;
-        println!("n: {}", n);
-        println!("a: {}", a);
-        println!("b: {}", b);
-
-        let (expected_a, expected_b) = fibonacci_lib::fibonacci(n);
-        assert_eq!(a, expected_a);
-        assert_eq!(b, expected_b);
+        // Read the output, and check it against the values computed natively.
+        let verified = check_public_values(output.as_slice(), args.exact).unwrap_or_else(|err| {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        });
+        print_verified(&verified);
         println!("Values are correct!");
 
         // Record the number of cycles executed.
         println!("Number of cycles: {}", report.total_instruction_count());
     } else {
+        let system = args.system.unwrap_or(ProofSystem::Groth16);
+
+        // Load an explicitly given verifying key up front so a bad path is reported before
+        // proving. The default key is only installed under `~/.sp1/circuits` by proving, so it is
+        // resolved afterwards. Mock proofs are not verified against a key.
+        let explicit_vk = args
+            .vk_path(system)
+            .filter(|_| !args.mock)
+            .map(|path| load_circuit_vk(system, Some(path)));
+
         // // Setup the program for proving.
-        let (pk, _) = client.setup(FIBONACCI_ELF);
+        let (pk, program_vk) = client.setup(FIBONACCI_ELF);
 
         // // Generate the proof
-        let proof = client
-            .prove(&pk, stdin)
-            .groth16()
-            .run()
-            .expect("failed to generate proof");
+        let proof = match system {
+            ProofSystem::Plonk => client.prove(&pk, stdin).plonk().run(),
+            ProofSystem::Groth16 => client.prove(&pk, stdin).groth16().run(),
+        }
+        .expect("failed to generate proof");
         println!("Successfully generated proof!");
 
-        // let proof = SP1ProofWithPublicValues::load("/Users/distractedm1nd/proof.bin").unwrap();
-        let vk =
-            std::fs::read("/Users/distractedm1nd/.sp1/circuits/v3.0.0-rc1/groth16_vk.bin").unwrap();
+        // Save the proof before verifying it, so it is kept even if verification fails.
+        if let Some(out) = &args.out {
+            save_proof(&proof, &program_vk, args.mock, out);
+        }
+
+        // Verify the proof.
+        let vk = (!args.mock).then(|| explicit_vk.unwrap_or_else(|| load_circuit_vk(system, None)));
+        let verified = match &vk {
+            Some(vk) => {
+                verify_fibonacci_proof(&proof, vk, system, &program_vk.bytes32(), args.exact)
+                    .unwrap_or_else(|err| {
+                        eprintln!("Error: failed to verify proof: {err}");
+                        std::process::exit(1);
+                    })
+            }
+            None => check_mock_proof(&client, &proof, &program_vk, args.exact),
+        };
+        if args.mock {
+            println!("Successfully checked the public inputs of the mock proof!");
+        } else {
+            println!("Successfully verified proof!");
+        }
+        print_verified(&verified);
+    }
+}
+
+/// Load the SP1 circuit's verifying key for `system`, exiting on failure.
+fn load_circuit_vk(system: ProofSystem, explicit: Option<&Path>) -> Vec<u8> {
+    fibonacci_verifier::circuit_vk(system, explicit).unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    })
+}
+
+/// Save a proof to `path`, along with a JSON summary next to it at `<stem>.summary.json`.
+fn save_proof(proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey, mock: bool, path: &Path) {
+    proof.save(path).expect("failed to save proof");
+
+    let summary = SavedProofSummary {
+        vkey: vk.bytes32(),
+        public_values: format!("0x{}", hex::encode(proof.public_values.as_slice())),
+        proof: format!("0x{}", hex::encode(proof.bytes())),
+        mock,
+    };
+    // Not just a `.json` extension, which would overwrite a proof saved as `proof.json`.
+    let summary_path = path.with_extension("summary.json");
+    std::fs::write(
+        &summary_path,
+        serde_json::to_string_pretty(&summary).unwrap(),
+    )
+    .expect("failed to write proof summary");
 
-        if let SP1Proof::Groth16(groth16_proof) = proof.proof {
-            dbg!(&groth16_proof.encoded_proof);
-            let raw_proof = hex::decode(&groth16_proof.encoded_proof).unwrap();
+    println!(
+        "Saved proof to {} and {}",
+        path.display(),
+        summary_path.display()
+    );
+}
 
-            let vkey_hash = BigUint::from_str(&groth16_proof.public_inputs[0]).unwrap();
-            let committed_values_digest =
-                BigUint::from_str(&groth16_proof.public_inputs[1]).unwrap();
+/// Check the public inputs and values of a mock proof, which has no proof to verify.
+fn check_mock_proof(
+    client: &ProverClient,
+    proof: &SP1ProofWithPublicValues,
+    program_vk: &SP1VerifyingKey,
+    exact: bool,
+) -> VerifiedExactFibonacci {
+    if let Err(err) = client.verify(proof, program_vk) {
+        eprintln!("Error: failed to verify mock proof: {err}");
+        std::process::exit(1);
+    }
+    check_public_values(proof.public_values.as_slice(), exact).unwrap_or_else(|err| {
+        eprintln!("Error: failed to verify mock proof: {err}");
+        std::process::exit(1);
+    })
+}
 
-            let pub_inputs = &[Fr::from(vkey_hash), Fr::from(committed_values_digest)];
+/// Verify a proof of the Fibonacci program in the given mode.
+fn verify_fibonacci_proof(
+    proof: &SP1ProofWithPublicValues,
+    vk: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+    exact: bool,
+) -> Result<VerifiedExactFibonacci, VerificationError> {
+    if exact {
+        fibonacci_verifier::verify_exact_fibonacci(proof, vk, system, program_vkey)
+    } else {
+        fibonacci_verifier::verify_fibonacci(proof, vk, system, program_vkey).map(Into::into)
+    }
+}
 
-            let res = gnark_bn254_verifier::verify(
-                &raw_proof,
-                &vk,
-                pub_inputs,
-                gnark_bn254_verifier::ProvingSystem::Groth16,
-            );
+/// Decode and check the public values committed by the Fibonacci program in the given mode.
+fn check_public_values(
+    public_values: &[u8],
+    exact: bool,
+) -> Result<VerifiedExactFibonacci, VerificationError> {
+    if exact {
+        fibonacci_verifier::check_exact_fibonacci_public_values(public_values)
+    } else {
+        fibonacci_verifier::check_fibonacci_public_values(public_values).map(Into::into)
+    }
+}
 
-            assert!(res)
-        } else {
-            panic!("wtf?");
+/// Verify a proof saved to disk against the circuit verifying key, without a prover.
+fn verify_saved_proof(
+    args: &Args,
+    proof_path: &Path,
+    vk_path: Option<&Path>,
+    program_vkey: Option<&str>,
+) {
+    let proof = fibonacci_verifier::load_proof(proof_path).unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    });
+    // An explicit `--system` is checked against the proof's when verifying it.
+    let system = args.system.unwrap_or_else(|| {
+        ProofSystem::of(&proof.proof).unwrap_or_else(|err| {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        })
+    });
+    let vk = load_circuit_vk(system, vk_path.or(args.vk_path(system)));
+
+    let program_vkey = expected_program_vkey(program_vkey);
+    let verified = verify_fibonacci_proof(&proof, &vk, system, &program_vkey, args.exact)
+        .unwrap_or_else(|err| {
+            eprintln!("Error: failed to verify proof: {err}");
+            std::process::exit(1);
+        });
+    println!("Successfully verified proof!");
+    print_verified(&verified);
+}
+
+/// Print the Fibonacci values attested to by a verified proof.
+fn print_verified(verified: &VerifiedExactFibonacci) {
+    println!("n: {}", verified.n);
+    println!("a: {}", verified.a);
+    println!("b: {}", verified.b);
+}
+
+/// Verify a fixture file written by the `evm` binary against the circuit verifying key.
+fn verify_fixture_file(
+    args: &Args,
+    path: &Path,
+    vk_path: Option<&Path>,
+    program_vkey: Option<&str>,
+) {
+    let fixture = SP1FibonacciProofFixture::load(path).unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    });
+    // A mock fixture's placeholder selector matches no key, so report it before inferring the
+    // proof system from the selector.
+    if fixture.mock {
+        eprintln!(
+            "Error: failed to verify fixture: {}",
+            VerificationError::MockProof
+        );
+        std::process::exit(1);
+    }
+    let (system, vk) = match args.system {
+        Some(system) => (
+            system,
+            load_circuit_vk(system, vk_path.or(args.vk_path(system))),
+        ),
+        None => infer_fixture_system(args, &fixture, vk_path),
+    };
+
+    let program_vkey = expected_program_vkey(program_vkey);
+    // Fixtures record their mode, so `--exact` is not needed to verify them.
+    let verified = if fixture.exact.is_some() {
+        fibonacci_verifier::verify_exact_fixture(&fixture, &vk, system, &program_vkey)
+    } else {
+        fibonacci_verifier::verify_fixture(&fixture, &vk, system, &program_vkey).map(Into::into)
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("Error: failed to verify fixture: {err}");
+        std::process::exit(1);
+    });
+    println!("Successfully verified fixture!");
+    print_verified(&verified);
+}
+
+/// Find the proof system of a fixture, and its verifying key, by matching the selector the
+/// fixture's proof starts with against the verifying key of each proof system.
+fn infer_fixture_system(
+    args: &Args,
+    fixture: &SP1FibonacciProofFixture,
+    vk_path: Option<&Path>,
+) -> (ProofSystem, Vec<u8>) {
+    let selector = fixture.selector().unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    });
+
+    for system in [ProofSystem::Groth16, ProofSystem::Plonk] {
+        // A key that is missing, or not a key of this system, just rules the system out.
+        let Ok(vk) = fibonacci_verifier::circuit_vk(system, vk_path.or(args.vk_path(system)))
+        else {
+            continue;
+        };
+        if fibonacci_verifier::circuit_selector(&vk, system).is_ok_and(|s| s == selector) {
+            return (system, vk);
         }
+    }
 
-        // Verify the proof.
-        // client.verify(&proof, &vk).expect("failed to verify proof");
-        println!("Successfully verified proof!");
+    eprintln!(
+        "Error: selector 0x{} of the fixture's proof matches no available verifying key, pass \
+         --system and --vk",
+        hex::encode(selector)
+    );
+    std::process::exit(1);
+}
+
+/// The program vkey hash proofs must be for. Only proofs of the Fibonacci program are accepted,
+/// unless another program is given.
+fn expected_program_vkey(explicit: Option<&str>) -> String {
+    match explicit {
+        Some(program_vkey) => program_vkey.to_string(),
+        // The vkey only depends on the program, so the mock prover computes it without setting up
+        // a real prover.
+        None => ProverClient::mock().setup(FIBONACCI_ELF).1.bytes32(),
     }
 }
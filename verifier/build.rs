@@ -0,0 +1,104 @@
+//! Embeds the SP1 circuit's Groth16 verifying key when the `embedded-vk` feature is enabled.
+//!
+//! The key is the one checked in at `circuits/<version>/groth16_vk.bin`, or `SP1_GROTH16_VK` if
+//! set, with the circuit version and the key's expected SHA-256 taken from `groth16_vk.pin`. The
+//! build fails unless the key matches the pinned hash, or `SP1_GROTH16_VK_SHA256` if set, so
+//! nothing found on the build machine is embedded unchecked.
+
+use sha2::{Digest, Sha256};
+use std::{env, fs, path::PathBuf};
+
+const PIN_FILE: &str = "groth16_vk.pin";
+
+fn main() {
+    println!("cargo:rerun-if-changed={PIN_FILE}");
+    println!("cargo:rerun-if-env-changed=SP1_GROTH16_VK");
+    println!("cargo:rerun-if-env-changed=SP1_GROTH16_VK_SHA256");
+
+    let pin = Pin::read();
+    println!("cargo:rustc-env=SP1_PINNED_CIRCUIT_VERSION={}", pin.version);
+
+    if env::var_os("CARGO_FEATURE_EMBEDDED_VK").is_none() {
+        return;
+    }
+
+    let vk_path = match env::var_os("SP1_GROTH16_VK") {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from("circuits")
+            .join(&pin.version)
+            .join("groth16_vk.bin"),
+    };
+    println!("cargo:rerun-if-changed={}", vk_path.display());
+
+    let vk = fs::read(&vk_path).unwrap_or_else(|err| {
+        panic!(
+            "failed to read Groth16 verifying key from {}: {err}; check in the circuit's \
+             groth16_vk.bin there or set SP1_GROTH16_VK",
+            vk_path.display()
+        )
+    });
+
+    let expected = match env::var("SP1_GROTH16_VK_SHA256") {
+        Ok(hash) => normalize_hash(&hash),
+        Err(_) => pin.sha256.unwrap_or_else(|| {
+            panic!(
+                "{PIN_FILE} records no Groth16 verifying key hash for circuit {}, record the \
+                 SHA-256 of its groth16_vk.bin there or set SP1_GROTH16_VK_SHA256",
+                pin.version
+            )
+        }),
+    };
+    let digest = Sha256::digest(&vk);
+    assert_eq!(
+        hex::encode(digest),
+        expected,
+        "Groth16 verifying key at {} does not match the pinned hash for circuit {}",
+        vk_path.display(),
+        pin.version
+    );
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("groth16_vk.bin"), &vk).expect("failed to write verifying key");
+    fs::write(out_dir.join("groth16_vk.sha256"), digest)
+        .expect("failed to write verifying key hash");
+}
+
+/// The contents of `groth16_vk.pin`.
+struct Pin {
+    version: String,
+    sha256: Option<String>,
+}
+
+impl Pin {
+    fn read() -> Self {
+        let contents = fs::read_to_string(PIN_FILE)
+            .unwrap_or_else(|err| panic!("failed to read {PIN_FILE}: {err}"));
+
+        let mut version = None;
+        let mut sha256 = None;
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .unwrap_or_else(|| panic!("malformed line in {PIN_FILE}: {line}"));
+            let value = value.trim();
+            match key.trim() {
+                "version" => version = Some(value.to_string()),
+                "sha256" if !value.is_empty() => sha256 = Some(normalize_hash(value)),
+                "sha256" => {}
+                key => panic!("unknown key in {PIN_FILE}: {key}"),
+            }
+        }
+
+        Self {
+            version: version.unwrap_or_else(|| panic!("{PIN_FILE} records no circuit version")),
+            sha256,
+        }
+    }
+}
+
+fn normalize_hash(hash: &str) -> String {
+    hash.trim().trim_start_matches("0x").to_lowercase()
+}
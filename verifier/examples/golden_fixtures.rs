@@ -0,0 +1,195 @@
+//! Regenerate the golden Groth16 fixtures in `tests/fixtures`.
+//!
+//! Real SP1 Groth16 proofs need a 128GB prover, so the golden proofs are instead real BN254
+//! Groth16 proofs of a small stand-in circuit with the same two public inputs as the SP1 wrapper
+//! circuit: the program vkey hash and the committed values digest. They are serialized exactly
+//! like SP1's, so they exercise the same decoding and verification paths.
+//!
+//! ```shell
+//! cargo run -p fibonacci-verifier --example golden_fixtures
+//! ```
+
+use alloy_sol_types::SolType;
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Groth16, Proof, ProvingKey};
+use ark_relations::{
+    lc,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+};
+use ark_serialize::CanonicalSerialize;
+use fibonacci_lib::PublicValuesStruct;
+use fibonacci_verifier::{hash_public_values, SP1FibonacciProofFixture};
+use num_bigint::BigUint;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use sha2::{Digest, Sha256};
+use sp1_core_machine::io::SP1PublicValues;
+use sp1_prover::Groth16Bn254Proof;
+use sp1_sdk::{
+    HashableKey, ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1_CIRCUIT_VERSION,
+};
+use std::path::PathBuf;
+
+/// The ELF of the Fibonacci program, whose vkey the golden proofs are for.
+const FIBONACCI_ELF: &[u8] = include_bytes!("../../elf/riscv32im-succinct-zkvm-elf");
+
+/// The input the golden proofs are for.
+const N: u32 = 10;
+
+/// A circuit with the public inputs of the SP1 wrapper circuit, and a single constraint on them.
+#[derive(Clone, Copy)]
+struct StandInCircuit {
+    vkey_hash: Option<Fr>,
+    committed_values_digest: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for StandInCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+        let vkey_hash = self.vkey_hash.ok_or(SynthesisError::AssignmentMissing);
+        let digest = self
+            .committed_values_digest
+            .ok_or(SynthesisError::AssignmentMissing);
+        let product = vkey_hash.and_then(|vkey_hash| Ok(vkey_hash * digest?));
+
+        let vkey_hash = cs.new_input_variable(|| vkey_hash)?;
+        let digest = cs.new_input_variable(|| digest)?;
+        let product = cs.new_witness_variable(|| product)?;
+        cs.enforce_constraint(lc!() + vkey_hash, lc!() + digest, lc!() + product)
+    }
+}
+
+fn main() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    // The public inputs of a proof of the Fibonacci program for `N`.
+    let (_, program_vk) = ProverClient::mock().setup(FIBONACCI_ELF);
+    let (a, b) = fibonacci_lib::fibonacci(N);
+    let public_values = PublicValuesStruct::abi_encode(&PublicValuesStruct { n: N, a, b });
+    let vkey_hash_int =
+        BigUint::parse_bytes(program_vk.bytes32().trim_start_matches("0x").as_bytes(), 16).unwrap();
+    let digest_int = hash_public_values(&public_values);
+    let vkey_hash = Fr::from(vkey_hash_int.clone());
+    let digest = Fr::from(digest_int.clone());
+
+    let setup = StandInCircuit {
+        vkey_hash: None,
+        committed_values_digest: None,
+    };
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(setup, &mut rng).unwrap();
+    let other_pk =
+        Groth16::<Bn254>::generate_random_parameters_with_reduction(setup, &mut rng).unwrap();
+
+    let circuit = StandInCircuit {
+        vkey_hash: Some(vkey_hash),
+        committed_values_digest: Some(digest),
+    };
+    let proof =
+        Groth16::<Bn254>::create_random_proof_with_reduction(circuit, &pk, &mut rng).unwrap();
+
+    let vk = gnark_vk_bytes(&pk);
+    std::fs::write(dir.join("groth16_vk.bin"), &vk).unwrap();
+    std::fs::write(dir.join("other_groth16_vk.bin"), gnark_vk_bytes(&other_pk)).unwrap();
+
+    // The proof as SP1 returns it from `client.prove(..).groth16()`.
+    let encoded_proof = gnark_solidity_proof_bytes(&proof);
+    let mut raw_proof = encoded_proof.clone();
+    // No commitments, and a commitment proof of knowledge at infinity.
+    raw_proof.extend_from_slice(&[0; 4]);
+    raw_proof.push(0b01 << 6);
+    raw_proof.extend_from_slice(&[0; 63]);
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&N);
+    stdin.write(&false);
+    let sp1_proof = SP1ProofWithPublicValues {
+        proof: SP1Proof::Groth16(Groth16Bn254Proof {
+            public_inputs: [vkey_hash_int.to_string(), digest_int.to_string()],
+            encoded_proof: hex::encode(&encoded_proof),
+            raw_proof: hex::encode(&raw_proof),
+            groth16_vkey_hash: Sha256::digest(&vk).into(),
+        }),
+        stdin,
+        public_values: SP1PublicValues::from(&public_values),
+        sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+    };
+    sp1_proof.save(dir.join("groth16-proof.bin")).unwrap();
+
+    // The fixture the `evm` binary writes for the same proof.
+    let fixture = SP1FibonacciProofFixture {
+        a,
+        b,
+        n: N,
+        vkey: program_vk.bytes32(),
+        public_values: format!("0x{}", hex::encode(&public_values)),
+        proof: format!("0x{}", hex::encode(sp1_proof.bytes())),
+        exact: None,
+        mock: false,
+    };
+    std::fs::write(
+        dir.join("groth16-fixture.json"),
+        serde_json::to_string_pretty(&fixture).unwrap(),
+    )
+    .unwrap();
+
+    println!("Wrote golden fixtures to {}", dir.display());
+}
+
+/// Serialize a verifying key like gnark's `groth16_vk.bin`, with no commitments.
+fn gnark_vk_bytes(pk: &ProvingKey<Bn254>) -> Vec<u8> {
+    let vk = &pk.vk;
+    let mut bytes = Vec::new();
+    bytes.extend(gnark_compressed(&vk.alpha_g1));
+    bytes.extend(gnark_compressed(&pk.beta_g1));
+    bytes.extend(gnark_compressed(&vk.beta_g2));
+    bytes.extend(gnark_compressed(&vk.gamma_g2));
+    bytes.extend(gnark_compressed(&pk.delta_g1));
+    bytes.extend(gnark_compressed(&vk.delta_g2));
+
+    bytes.extend((vk.gamma_abc_g1.len() as u32).to_be_bytes());
+    for k in &vk.gamma_abc_g1 {
+        bytes.extend(gnark_compressed(k));
+    }
+
+    // No public and commitment committed wires, and an unused Pedersen commitment key.
+    bytes.extend(0u32.to_be_bytes());
+    let g = G2Affine::generator();
+    bytes.extend(gnark_compressed(&g));
+    bytes.extend(gnark_compressed(&(-(g * Fr::from(2u64))).into_affine()));
+    bytes
+}
+
+/// Serialize a point compressed the way gnark does: big-endian, with the flags in the top two
+/// bits of the first byte.
+fn gnark_compressed(point: &impl CanonicalSerialize) -> Vec<u8> {
+    // arkworks serializes little-endian with its own flags in the top two bits of the last byte.
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes.reverse();
+    let flag = match bytes[0] >> 6 {
+        0b00 => 0b10,
+        0b10 => 0b11,
+        0b01 => 0b01,
+        flag => panic!("unexpected arkworks flag {flag:#b}"),
+    };
+    bytes[0] = (bytes[0] & 0b0011_1111) | (flag << 6);
+    bytes
+}
+
+/// Serialize a proof like gnark's `MarshalSolidity`: A, B and C uncompressed, as big-endian words.
+fn gnark_solidity_proof_bytes(proof: &Proof<Bn254>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(g1_words(&proof.a));
+    let (x, y) = proof.b.xy().unwrap();
+    for coordinate in [x.c1, x.c0, y.c1, y.c0] {
+        bytes.extend(coordinate.into_bigint().to_bytes_be());
+    }
+    bytes.extend(g1_words(&proof.c));
+    bytes
+}
+
+fn g1_words(point: &G1Affine) -> Vec<u8> {
+    let (x, y) = point.xy().unwrap();
+    [x.into_bigint().to_bytes_be(), y.into_bigint().to_bytes_be()].concat()
+}
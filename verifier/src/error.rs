@@ -0,0 +1,188 @@
+use crate::{PointError, ProofPoint, ProofSystem, VerifiedFibonacci};
+use num_bigint::BigUint;
+use sp1_sdk::SP1ProofKind;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// The reasons a proof can fail local verification.
+#[derive(Debug, Error)]
+pub enum VerificationError {
+    /// The encoded proof is not valid hex, or could not be decoded into curve points.
+    #[error("malformed proof encoding: {0}")]
+    MalformedProof(String),
+
+    /// A point of a Groth16 proof is not a valid point of the prime order subgroup.
+    #[error("invalid proof point {point}: {reason}")]
+    InvalidProofPoint {
+        point: ProofPoint,
+        reason: PointError,
+    },
+
+    /// A public input is not a decimal integer.
+    #[error("malformed public input {index}: {value:?}")]
+    MalformedPublicInput { index: usize, value: String },
+
+    /// A public input is not below the BN254 scalar field modulus, so it is not the canonical
+    /// encoding of a scalar.
+    #[error("public input {index} ({value}) is not below the BN254 scalar field modulus")]
+    NonCanonicalPublicInput { index: usize, value: BigUint },
+
+    /// The number of public inputs does not match the verifying key.
+    #[error("expected {expected} public inputs, got {actual}")]
+    PublicInputCount { expected: usize, actual: usize },
+
+    /// The proof file could not be read or deserialized.
+    #[error("failed to load proof from {}: {source}", path.display())]
+    ProofLoad {
+        path: PathBuf,
+        #[source]
+        source: bincode::Error,
+    },
+
+    /// The fixture file could not be read or deserialized.
+    #[error("failed to load fixture from {}: {source}", path.display())]
+    FixtureLoad {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// The expected program vkey is not a `0x`-prefixed 32 byte hex string.
+    #[error("malformed program vkey {0:?}, expected 32 bytes of hex")]
+    MalformedProgramVkey(String),
+
+    /// The proof is for a different program than the expected one.
+    #[error("proof is for program vkey {actual}, expected {expected}")]
+    ProgramVkeyMismatch { expected: String, actual: String },
+
+    /// The proof is not of a variant that can be verified locally.
+    #[error("unsupported proof variant {0:?}, expected Plonk or Groth16")]
+    UnsupportedProof(SP1ProofKind),
+
+    /// The proof uses a different proof system than the one being verified.
+    #[error("expected a {expected:?} proof, got a {actual:?} proof")]
+    WrongProofSystem {
+        expected: ProofSystem,
+        actual: ProofSystem,
+    },
+
+    /// None of the candidate verifying key locations exist.
+    #[error(
+        "no {system:?} verifying key found (tried: {}); set {} to its location",
+        display_paths(.tried),
+        .system.vk_env_var()
+    )]
+    VkNotFound {
+        system: ProofSystem,
+        tried: Vec<PathBuf>,
+    },
+
+    /// The verifying key does not have the layout of gnark's `groth16_vk.bin`.
+    #[error("malformed verifying key: {0}")]
+    MalformedVk(String),
+
+    /// A point of a Groth16 verifying key is not a valid point of the prime order subgroup.
+    #[error("invalid verifying key point {point}: {reason}")]
+    InvalidVkPoint { point: String, reason: PointError },
+
+    /// The verifying key could not be read.
+    #[error("failed to load verifying key from {}: {source}", path.display())]
+    VkLoad {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The verifying key is not the one the proof was generated against.
+    #[error(
+        "verifying key hash 0x{} does not match the proof's circuit vkey hash 0x{}",
+        hex::encode(.actual),
+        hex::encode(.expected)
+    )]
+    VkMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+
+    /// Onchain proof bytes carry the selector of a different circuit verifying key.
+    #[error(
+        "proof selector 0x{} does not match verifying key selector 0x{}",
+        hex::encode(.actual),
+        hex::encode(.expected)
+    )]
+    SelectorMismatch { expected: [u8; 4], actual: [u8; 4] },
+
+    /// The committed values digest is not the hash of the proof's public values.
+    #[error("committed values digest {committed} does not match public values hash {actual}")]
+    PublicValuesMismatch { committed: BigUint, actual: BigUint },
+
+    /// The public values are not an ABI encoded `PublicValuesStruct`.
+    #[error("malformed public values: {0}")]
+    MalformedPublicValues(String),
+
+    /// The public values claim Fibonacci numbers that are not the ones for `n`.
+    #[error("public values claim fibonacci({n}) = ({a}, {b}), which is incorrect")]
+    IncorrectFibonacci { n: u32, a: u32, b: u32 },
+
+    /// The public values of the exact mode claim Fibonacci numbers that are not the ones for `n`.
+    #[error("public values claim incorrect exact fibonacci numbers for n = {n}")]
+    IncorrectExactFibonacci { n: u32, a: BigUint, b: BigUint },
+
+    /// A fixture's Fibonacci values differ from the ones committed to in its public values.
+    #[error("fixture claims {claimed:?}, but its public values commit to {verified:?}")]
+    FixtureMismatch {
+        claimed: VerifiedFibonacci,
+        verified: VerifiedFibonacci,
+    },
+
+    /// The proof is a placeholder from the mock prover, so there is nothing to verify.
+    #[error("mock proofs cannot be verified")]
+    MockProof,
+
+    /// The proof is an onchain PLONK proof, whose Solidity encoding gnark cannot parse.
+    #[error("onchain PLONK proofs cannot be verified locally, verify the saved proof instead")]
+    OnchainPlonkProof,
+
+    /// A fixture's exact Fibonacci values differ from the ones committed to in its public values.
+    #[error("fixture claims exact values for n = {n} that its public values do not commit to")]
+    ExactFixtureMismatch { n: u32 },
+
+    /// A fixture is of the other mode than the one being verified.
+    #[error(
+        "expected a fixture of the {} mode, got one of the {} mode",
+        mode_name(*.expected_exact),
+        mode_name(!.expected_exact)
+    )]
+    FixtureModeMismatch { expected_exact: bool },
+
+    /// A proof of a batch failed verification, found after the batch as a whole did.
+    #[error("proof {index} of the batch failed verification: {source}")]
+    BatchProofRejected {
+        index: usize,
+        #[source]
+        source: Box<VerificationError>,
+    },
+
+    /// The proof was decoded successfully but failed the pairing check.
+    #[error("proof rejected by the {0:?} verifier")]
+    Rejected(ProofSystem),
+}
+
+fn mode_name(exact: bool) -> &'static str {
+    if exact {
+        "exact"
+    } else {
+        "wrapping"
+    }
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return "no home directory to search".to_string();
+    }
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,119 @@
+use crate::{
+    check_canonical, groth16, hash_public_values, parse_program_vkey, verify_encoded_proof,
+    ProofSystem, VerificationError,
+};
+use gnark_bn254_verifier::Fr;
+use sha2::{Digest, Sha256};
+
+/// The length of the verifier selector that prefixes onchain proof bytes.
+pub const SELECTOR_LEN: usize = 4;
+
+/// The length of an onchain Groth16 proof after the selector: the A, B and C points as eight
+/// 32 byte words.
+pub const GROTH16_EVM_PROOF_LEN: usize = 256;
+
+/// Proof bytes in the format the onchain SP1 verifier accepts, as returned by
+/// [`SP1ProofWithPublicValues::bytes`](sp1_sdk::SP1ProofWithPublicValues::bytes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmProof {
+    /// The first 4 bytes of the SHA-256 of the circuit verifying key the proof is for.
+    pub selector: [u8; SELECTOR_LEN],
+    /// The proof after the selector. For Groth16 these are the uncompressed A, B and C points
+    /// gnark parses, for PLONK the Solidity verifier's encoding, which gnark cannot parse.
+    pub encoded_proof: Vec<u8>,
+}
+
+impl EvmProof {
+    /// Decode onchain proof bytes, checking that their selector matches the given circuit
+    /// verifying key.
+    pub fn decode(
+        proof_bytes: &[u8],
+        vk_bytes: &[u8],
+        system: ProofSystem,
+    ) -> Result<Self, VerificationError> {
+        if proof_bytes.len() < SELECTOR_LEN {
+            return Err(VerificationError::MalformedProof(format!(
+                "proof is shorter than the {SELECTOR_LEN} byte verifier selector"
+            )));
+        }
+        let (selector, encoded_proof) = proof_bytes.split_at(SELECTOR_LEN);
+        let selector: [u8; SELECTOR_LEN] = selector.try_into().unwrap();
+
+        let expected = circuit_selector(vk_bytes, system)?;
+        if selector != expected {
+            return Err(VerificationError::SelectorMismatch {
+                expected,
+                actual: selector,
+            });
+        }
+
+        let well_formed = match system {
+            ProofSystem::Groth16 => encoded_proof.len() == GROTH16_EVM_PROOF_LEN,
+            ProofSystem::Plonk => !encoded_proof.is_empty() && encoded_proof.len() % 32 == 0,
+        };
+        if !well_formed {
+            return Err(VerificationError::MalformedProof(format!(
+                "unexpected {system:?} proof length of {} bytes",
+                encoded_proof.len()
+            )));
+        }
+
+        Ok(Self {
+            selector,
+            encoded_proof: encoded_proof.to_vec(),
+        })
+    }
+}
+
+/// The selector onchain proofs for a circuit verifying key start with: the first 4 bytes of the
+/// key's SHA-256.
+pub fn verifier_selector(vk_bytes: &[u8]) -> [u8; SELECTOR_LEN] {
+    let hash = Sha256::digest(vk_bytes);
+    hash[..SELECTOR_LEN].try_into().unwrap()
+}
+
+/// The selector onchain proofs for a circuit verifying key in either point encoding start with.
+pub fn circuit_selector(
+    vk_bytes: &[u8],
+    system: ProofSystem,
+) -> Result<[u8; SELECTOR_LEN], VerificationError> {
+    Ok(verifier_selector(&groth16::sp1_vk_bytes(vk_bytes, system)?))
+}
+
+/// The public inputs an onchain proof is verified against: the program vkey hash and the digest
+/// of the public values.
+pub fn evm_public_inputs(
+    program_vkey: &str,
+    public_values: &[u8],
+) -> Result<[Fr; 2], VerificationError> {
+    let vkey_hash = parse_program_vkey(program_vkey)?;
+    Ok([
+        Fr::from(check_canonical(0, vkey_hash)?),
+        Fr::from(check_canonical(1, hash_public_values(public_values))?),
+    ])
+}
+
+/// Verify onchain proof bytes for a program and its public values, the way the SP1 verifier
+/// contract does.
+///
+/// Only Groth16 proofs can be verified: onchain PLONK proofs are rejected with
+/// [`VerificationError::OnchainPlonkProof`] once their selector is checked.
+pub fn verify_evm_proof(
+    proof_bytes: &[u8],
+    public_values: &[u8],
+    program_vkey: &str,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+) -> Result<(), VerificationError> {
+    let vk_bytes = groth16::sp1_vk_bytes(vk_bytes, system)?;
+    let proof = EvmProof::decode(proof_bytes, &vk_bytes, system)?;
+    if system == ProofSystem::Plonk {
+        return Err(VerificationError::OnchainPlonkProof);
+    }
+    verify_encoded_proof(
+        &proof.encoded_proof,
+        &vk_bytes,
+        evm_public_inputs(program_vkey, public_values)?,
+        system,
+    )
+}
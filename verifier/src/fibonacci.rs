@@ -0,0 +1,102 @@
+use crate::{verify_program_vkey, verify_proof, ProofSystem, VerificationError};
+use alloy_sol_types::SolType;
+use fibonacci_lib::{ExactPublicValuesStruct, PublicValuesStruct};
+use num_bigint::BigUint;
+use sp1_sdk::SP1ProofWithPublicValues;
+
+/// The Fibonacci values attested to by a verified proof of the Fibonacci program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifiedFibonacci {
+    pub n: u32,
+    pub a: u32,
+    pub b: u32,
+}
+
+/// The Fibonacci values attested to by a verified proof of the Fibonacci program's exact mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedExactFibonacci {
+    pub n: u32,
+    pub a: BigUint,
+    pub b: BigUint,
+}
+
+impl VerifiedExactFibonacci {
+    /// The values reduced mod 2^32, as the wrapping mode commits to them.
+    pub fn wrapped(&self) -> VerifiedFibonacci {
+        let low_word = |value: &BigUint| value.iter_u32_digits().next().unwrap_or(0);
+        VerifiedFibonacci {
+            n: self.n,
+            a: low_word(&self.a),
+            b: low_word(&self.b),
+        }
+    }
+}
+
+impl From<VerifiedFibonacci> for VerifiedExactFibonacci {
+    fn from(verified: VerifiedFibonacci) -> Self {
+        Self {
+            n: verified.n,
+            a: verified.a.into(),
+            b: verified.b.into(),
+        }
+    }
+}
+
+/// Verify a proof of the Fibonacci program with the given vkey hash, and return the values it
+/// commits to once they are checked against [`fibonacci_lib::fibonacci`].
+pub fn verify_fibonacci(
+    proof: &SP1ProofWithPublicValues,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+) -> Result<VerifiedFibonacci, VerificationError> {
+    verify_proof(proof, vk_bytes, system)?;
+    verify_program_vkey(proof, program_vkey)?;
+    check_fibonacci_public_values(proof.public_values.as_slice())
+}
+
+/// Decode the public values committed by the Fibonacci program and check that they are correct.
+pub fn check_fibonacci_public_values(
+    public_values: &[u8],
+) -> Result<VerifiedFibonacci, VerificationError> {
+    let PublicValuesStruct { n, a, b } = PublicValuesStruct::abi_decode(public_values, true)
+        .map_err(|err| VerificationError::MalformedPublicValues(err.to_string()))?;
+
+    let (expected_a, expected_b) = fibonacci_lib::fibonacci(n);
+    if (a, b) != (expected_a, expected_b) {
+        return Err(VerificationError::IncorrectFibonacci { n, a, b });
+    }
+
+    Ok(VerifiedFibonacci { n, a, b })
+}
+
+/// Like [`verify_fibonacci`], for proofs of the exact mode.
+pub fn verify_exact_fibonacci(
+    proof: &SP1ProofWithPublicValues,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+) -> Result<VerifiedExactFibonacci, VerificationError> {
+    verify_proof(proof, vk_bytes, system)?;
+    verify_program_vkey(proof, program_vkey)?;
+    check_exact_fibonacci_public_values(proof.public_values.as_slice())
+}
+
+/// Decode the public values committed by the Fibonacci program's exact mode and check that they
+/// are correct.
+pub fn check_exact_fibonacci_public_values(
+    public_values: &[u8],
+) -> Result<VerifiedExactFibonacci, VerificationError> {
+    let ExactPublicValuesStruct { n, a, b } =
+        ExactPublicValuesStruct::abi_decode(public_values, true)
+            .map_err(|err| VerificationError::MalformedPublicValues(err.to_string()))?;
+    let a = BigUint::from_bytes_be(&a);
+    let b = BigUint::from_bytes_be(&b);
+
+    let (expected_a, expected_b) = fibonacci_lib::fibonacci_exact(n);
+    if (&a, &b) != (&expected_a, &expected_b) {
+        return Err(VerificationError::IncorrectExactFibonacci { n, a, b });
+    }
+
+    Ok(VerifiedExactFibonacci { n, a, b })
+}
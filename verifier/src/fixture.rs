@@ -0,0 +1,158 @@
+use crate::{
+    check_exact_fibonacci_public_values, check_fibonacci_public_values, parse_program_vkey,
+    verify_evm_proof, ProofSystem, VerificationError, VerifiedExactFibonacci, VerifiedFibonacci,
+    SELECTOR_LEN,
+};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, path::Path};
+
+/// A fixture that can be used to test the verification of SP1 zkVM proofs inside Solidity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SP1FibonacciProofFixture {
+    pub a: u32,
+    pub b: u32,
+    pub n: u32,
+    pub vkey: String,
+    pub public_values: String,
+    pub proof: String,
+    /// The exact values of `a` and `b` for a proof of the exact mode, in which case `a` and `b`
+    /// hold them mod 2^32.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact: Option<ExactFibonacciValues>,
+    /// Whether `proof` is a placeholder from the mock prover rather than a real proof.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub mock: bool,
+}
+
+/// The exact Fibonacci values of a fixture of the exact mode, in decimal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExactFibonacciValues {
+    pub a: String,
+    pub b: String,
+}
+
+impl SP1FibonacciProofFixture {
+    /// Load a fixture from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VerificationError> {
+        let path = path.as_ref();
+        File::open(path)
+            .map_err(serde_json::Error::io)
+            .and_then(serde_json::from_reader)
+            .map_err(|source| VerificationError::FixtureLoad {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// The verifier selector the fixture's proof starts with, which identifies the circuit
+    /// verifying key, and so the proof system, it is for.
+    pub fn selector(&self) -> Result<[u8; SELECTOR_LEN], VerificationError> {
+        let proof_bytes = self.proof_bytes()?;
+        proof_bytes
+            .get(..SELECTOR_LEN)
+            .and_then(|selector| selector.try_into().ok())
+            .ok_or_else(|| {
+                VerificationError::MalformedProof(format!(
+                    "proof is shorter than the {SELECTOR_LEN} byte verifier selector"
+                ))
+            })
+    }
+
+    fn proof_bytes(&self) -> Result<Vec<u8>, VerificationError> {
+        hex::decode(self.proof.trim_start_matches("0x"))
+            .map_err(|err| VerificationError::MalformedProof(err.to_string()))
+    }
+}
+
+/// Verify a fixture locally from the same fields the Solidity verifier consumes, and check that
+/// its Fibonacci values are the ones committed to in its public values. Only Groth16 fixtures can
+/// be verified, see [`verify_evm_proof`].
+pub fn verify_fixture(
+    fixture: &SP1FibonacciProofFixture,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+) -> Result<VerifiedFibonacci, VerificationError> {
+    if fixture.exact.is_some() {
+        return Err(VerificationError::FixtureModeMismatch {
+            expected_exact: false,
+        });
+    }
+    let public_values = verify_fixture_proof(fixture, vk_bytes, system, program_vkey)?;
+
+    let verified = check_fibonacci_public_values(&public_values)?;
+    check_claimed_values(fixture, verified)?;
+    Ok(verified)
+}
+
+/// Like [`verify_fixture`], for fixtures of the exact mode.
+pub fn verify_exact_fixture(
+    fixture: &SP1FibonacciProofFixture,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+) -> Result<VerifiedExactFibonacci, VerificationError> {
+    let exact = fixture
+        .exact
+        .as_ref()
+        .ok_or(VerificationError::FixtureModeMismatch {
+            expected_exact: true,
+        })?;
+    let public_values = verify_fixture_proof(fixture, vk_bytes, system, program_vkey)?;
+
+    let verified = check_exact_fibonacci_public_values(&public_values)?;
+    check_claimed_values(fixture, verified.wrapped())?;
+    if exact.a != verified.a.to_string() || exact.b != verified.b.to_string() {
+        return Err(VerificationError::ExactFixtureMismatch { n: verified.n });
+    }
+    Ok(verified)
+}
+
+/// Verify a fixture's proof for the expected program, and return its public values.
+fn verify_fixture_proof(
+    fixture: &SP1FibonacciProofFixture,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+    program_vkey: &str,
+) -> Result<Vec<u8>, VerificationError> {
+    if fixture.mock {
+        return Err(VerificationError::MockProof);
+    }
+
+    let expected_vkey = parse_program_vkey(program_vkey)?;
+    if parse_program_vkey(&fixture.vkey)? != expected_vkey {
+        return Err(VerificationError::ProgramVkeyMismatch {
+            expected: program_vkey.to_string(),
+            actual: fixture.vkey.clone(),
+        });
+    }
+
+    let public_values = hex::decode(fixture.public_values.trim_start_matches("0x"))
+        .map_err(|err| VerificationError::MalformedPublicValues(err.to_string()))?;
+    let proof_bytes = fixture.proof_bytes()?;
+    verify_evm_proof(
+        &proof_bytes,
+        &public_values,
+        &fixture.vkey,
+        vk_bytes,
+        system,
+    )?;
+    Ok(public_values)
+}
+
+/// Check a fixture's `n`, `a` and `b` against the values its public values commit to.
+fn check_claimed_values(
+    fixture: &SP1FibonacciProofFixture,
+    verified: VerifiedFibonacci,
+) -> Result<(), VerificationError> {
+    let claimed = VerifiedFibonacci {
+        n: fixture.n,
+        a: fixture.a,
+        b: fixture.b,
+    };
+    if verified != claimed {
+        return Err(VerificationError::FixtureMismatch { claimed, verified });
+    }
+    Ok(())
+}
@@ -0,0 +1,579 @@
+use crate::{ProofSystem, VerificationError};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr,
+};
+use ark_ff::{BigInteger, BigInteger256, PrimeField};
+use num_bigint::BigUint;
+use std::{borrow::Cow, fmt};
+use thiserror::Error;
+
+/// The length of a compressed G1 point: the big-endian x coordinate, with flags in its top bits.
+pub const G1_COMPRESSED_LEN: usize = 32;
+
+/// The length of a compressed G2 point: the big-endian x coordinate, with flags in its top bits.
+pub const G2_COMPRESSED_LEN: usize = 64;
+
+/// The length of an uncompressed G1 point: two big-endian base field words.
+pub const G1_UNCOMPRESSED_LEN: usize = 64;
+
+/// The length of an uncompressed G2 point: four big-endian base field words.
+pub const G2_UNCOMPRESSED_LEN: usize = 128;
+
+/// The flags gnark stores in the top two bits of the first byte of a point.
+const FLAG_MASK: u8 = 0b11 << 6;
+const FLAG_UNCOMPRESSED_INFINITY: u8 = 0b01 << 6;
+const FLAG_COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+const FLAG_COMPRESSED_LARGEST: u8 = 0b11 << 6;
+const FLAG_COMPRESSED_INFINITY: u8 = 0b01 << 6;
+
+/// How the curve points of a Groth16 proof or verifying key are encoded, following gnark.
+///
+/// SP1's `groth16_vk.bin` is compressed, and its proofs are uncompressed, as the onchain verifier
+/// expects them. Other gnark tooling may use either.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PointEncoding {
+    /// Only the x coordinate, with flags selecting y.
+    Compressed,
+    /// Both coordinates.
+    Uncompressed,
+}
+
+impl PointEncoding {
+    /// The length of an encoded G1 point.
+    pub fn g1_len(self) -> usize {
+        match self {
+            Self::Compressed => G1_COMPRESSED_LEN,
+            Self::Uncompressed => G1_UNCOMPRESSED_LEN,
+        }
+    }
+
+    /// The length of an encoded G2 point.
+    pub fn g2_len(self) -> usize {
+        match self {
+            Self::Compressed => G2_COMPRESSED_LEN,
+            Self::Uncompressed => G2_UNCOMPRESSED_LEN,
+        }
+    }
+
+    /// The length of an encoded Groth16 proof, `A || B || C`.
+    pub fn proof_len(self) -> usize {
+        2 * self.g1_len() + self.g2_len()
+    }
+
+    /// Detect the encoding of a Groth16 proof from its length.
+    pub fn of_proof(bytes: &[u8]) -> Result<Self, VerificationError> {
+        [Self::Uncompressed, Self::Compressed]
+            .into_iter()
+            .find(|encoding| encoding.proof_len() == bytes.len())
+            .ok_or_else(|| {
+                VerificationError::MalformedProof(format!(
+                    "expected a {} or {} byte Groth16 proof, got {} bytes",
+                    Self::Uncompressed.proof_len(),
+                    Self::Compressed.proof_len(),
+                    bytes.len()
+                ))
+            })
+    }
+
+    /// Detect the encoding of a Groth16 verifying key from its layout.
+    pub fn of_vk(bytes: &[u8]) -> Result<Self, VerificationError> {
+        // Walked with the wrong encoding, the key's counts are read from inside its point data,
+        // which all but never describes a layout that fits in the key.
+        [Self::Compressed, Self::Uncompressed]
+            .into_iter()
+            .find(|&encoding| vk_len(bytes, encoding).is_some_and(|len| len <= bytes.len()))
+            .ok_or_else(|| {
+                VerificationError::MalformedVk(
+                    "layout matches neither the compressed nor the uncompressed encoding"
+                        .to_string(),
+                )
+            })
+    }
+}
+
+/// A Groth16 proof decoded into curve points. Each point is a valid, finite point in the prime
+/// order subgroup, as checked by [`Groth16Proof::new`] and the decoders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Groth16Proof {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+}
+
+/// The points of a Groth16 proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofPoint {
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for ProofPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProofPoint::A => "A",
+            ProofPoint::B => "B",
+            ProofPoint::C => "C",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The reasons an encoded point can fail to decode into a valid curve point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PointError {
+    /// The flags in the top bits of the point are not valid for its encoding.
+    #[error("invalid encoding flags")]
+    InvalidFlags,
+
+    /// A coordinate is not below the base field modulus.
+    #[error("coordinate is not below the base field modulus")]
+    NonCanonicalCoordinate,
+
+    /// The point is the point at infinity, which no honest proof contains.
+    #[error("point at infinity")]
+    Infinity,
+
+    /// The coordinates do not satisfy the curve equation.
+    #[error("not on the curve")]
+    NotOnCurve,
+
+    /// The point is on the curve, but outside the prime order subgroup.
+    #[error("not in the prime order subgroup")]
+    NotInSubgroup,
+}
+
+impl Groth16Proof {
+    /// Build a proof from its points, checking that each is a valid, finite point in the prime
+    /// order subgroup.
+    pub fn new(a: G1Affine, b: G2Affine, c: G1Affine) -> Result<Self, VerificationError> {
+        let invalid = |point| move |reason| VerificationError::InvalidProofPoint { point, reason };
+        Ok(Self {
+            a: valid(a).map_err(invalid(ProofPoint::A))?,
+            b: valid(b).map_err(invalid(ProofPoint::B))?,
+            c: valid(c).map_err(invalid(ProofPoint::C))?,
+        })
+    }
+
+    /// The `A` point.
+    pub fn a(&self) -> G1Affine {
+        self.a
+    }
+
+    /// The `B` point.
+    pub fn b(&self) -> G2Affine {
+        self.b
+    }
+
+    /// The `C` point.
+    pub fn c(&self) -> G1Affine {
+        self.c
+    }
+
+    /// Decode a proof in either of gnark's encodings, `A || B || C`, detecting which from its
+    /// length. Each point must be a valid, finite point in the prime order subgroup.
+    pub fn decode(bytes: &[u8]) -> Result<Self, VerificationError> {
+        Self::decode_with(bytes, PointEncoding::of_proof(bytes)?)
+    }
+
+    /// Like [`Groth16Proof::decode`], for a proof in the given encoding.
+    pub fn decode_with(bytes: &[u8], encoding: PointEncoding) -> Result<Self, VerificationError> {
+        if bytes.len() != encoding.proof_len() {
+            return Err(VerificationError::MalformedProof(format!(
+                "expected a {} byte {encoding:?} Groth16 proof, got {} bytes",
+                encoding.proof_len(),
+                bytes.len()
+            )));
+        }
+        let (a, rest) = bytes.split_at(encoding.g1_len());
+        let (b, c) = rest.split_at(encoding.g2_len());
+
+        let invalid = |point| move |reason| VerificationError::InvalidProofPoint { point, reason };
+        Ok(Self {
+            a: decode_g1(a, encoding)
+                .and_then(finite)
+                .map_err(invalid(ProofPoint::A))?,
+            b: decode_g2(b, encoding)
+                .and_then(finite)
+                .map_err(invalid(ProofPoint::B))?,
+            c: decode_g1(c, encoding)
+                .and_then(finite)
+                .map_err(invalid(ProofPoint::C))?,
+        })
+    }
+
+    /// Encode the proof as `A || B || C` in the given encoding.
+    pub fn to_bytes(&self, encoding: PointEncoding) -> Vec<u8> {
+        [
+            encode_g1(&self.a, encoding),
+            encode_g2(&self.b, encoding),
+            encode_g1(&self.c, encoding),
+        ]
+        .concat()
+    }
+}
+
+/// A Groth16 verifying key decoded into curve points, in the layout of gnark's
+/// `groth16_vk.bin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groth16Vk {
+    pub alpha_g1: G1Affine,
+    pub beta_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g1: G1Affine,
+    pub delta_g2: G2Affine,
+    /// The points the public inputs are combined over, the first for the constant term.
+    pub k: Vec<G1Affine>,
+    /// For each commitment, the indices of the public inputs it commits to.
+    pub public_and_commitment_committed: Vec<Vec<u32>>,
+    /// The Pedersen commitment key, `G` and `G^(-1/sigma)`.
+    pub commitment_key: [G2Affine; 2],
+}
+
+impl Groth16Vk {
+    /// Decode a verifying key in either of gnark's encodings, detecting which from its layout.
+    pub fn decode(bytes: &[u8]) -> Result<Self, VerificationError> {
+        Self::decode_with(bytes, PointEncoding::of_vk(bytes)?)
+    }
+
+    /// Like [`Groth16Vk::decode`], for a key in the given encoding. Bytes after the commitment
+    /// key are ignored, as gnark does.
+    pub fn decode_with(bytes: &[u8], encoding: PointEncoding) -> Result<Self, VerificationError> {
+        let mut reader = VkReader { bytes, encoding };
+        let alpha_g1 = reader.g1("alpha_g1")?;
+        let beta_g1 = reader.g1("beta_g1")?;
+        let beta_g2 = reader.g2("beta_g2")?;
+        let gamma_g2 = reader.g2("gamma_g2")?;
+        let delta_g1 = reader.g1("delta_g1")?;
+        let delta_g2 = reader.g2("delta_g2")?;
+
+        let num_k = reader.u32()?;
+        let k = (0..num_k)
+            .map(|index| reader.g1(&format!("k[{index}]")))
+            .collect::<Result<_, _>>()?;
+
+        let num_committed = reader.u32()?;
+        let public_and_commitment_committed = (0..num_committed)
+            .map(|_| {
+                let len = reader.u32()?;
+                (0..len).map(|_| reader.u32()).collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let commitment_key = [
+            reader.g2("commitment_key.g")?,
+            reader.g2("commitment_key.g_root_sigma_neg")?,
+        ];
+
+        Ok(Self {
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g1,
+            delta_g2,
+            k,
+            public_and_commitment_committed,
+            commitment_key,
+        })
+    }
+
+    /// Encode the key in the layout of gnark's `groth16_vk.bin`, with points in the given
+    /// encoding.
+    pub fn to_bytes(&self, encoding: PointEncoding) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(encode_g1(&self.alpha_g1, encoding));
+        bytes.extend(encode_g1(&self.beta_g1, encoding));
+        bytes.extend(encode_g2(&self.beta_g2, encoding));
+        bytes.extend(encode_g2(&self.gamma_g2, encoding));
+        bytes.extend(encode_g1(&self.delta_g1, encoding));
+        bytes.extend(encode_g2(&self.delta_g2, encoding));
+
+        bytes.extend((self.k.len() as u32).to_be_bytes());
+        for k in &self.k {
+            bytes.extend(encode_g1(k, encoding));
+        }
+
+        bytes.extend((self.public_and_commitment_committed.len() as u32).to_be_bytes());
+        for committed in &self.public_and_commitment_committed {
+            bytes.extend((committed.len() as u32).to_be_bytes());
+            for index in committed {
+                bytes.extend(index.to_be_bytes());
+            }
+        }
+
+        for point in &self.commitment_key {
+            bytes.extend(encode_g2(point, encoding));
+        }
+        bytes
+    }
+}
+
+/// Re-encode a Groth16 proof in the given encoding, detecting its current one.
+pub fn convert_groth16_proof(
+    bytes: &[u8],
+    to: PointEncoding,
+) -> Result<Vec<u8>, VerificationError> {
+    Ok(Groth16Proof::decode(bytes)?.to_bytes(to))
+}
+
+/// Re-encode a Groth16 verifying key in the given encoding, detecting its current one.
+pub fn convert_groth16_vk(bytes: &[u8], to: PointEncoding) -> Result<Vec<u8>, VerificationError> {
+    Ok(Groth16Vk::decode(bytes)?.to_bytes(to))
+}
+
+/// A verifying key in the encoding SP1 hashes and the gnark verifier parses: as is for PLONK, and
+/// compressed for Groth16.
+pub(crate) fn sp1_vk_bytes(
+    vk_bytes: &[u8],
+    system: ProofSystem,
+) -> Result<Cow<'_, [u8]>, VerificationError> {
+    match system {
+        ProofSystem::Groth16 if PointEncoding::of_vk(vk_bytes)? == PointEncoding::Uncompressed => {
+            Ok(Cow::Owned(convert_groth16_vk(
+                vk_bytes,
+                PointEncoding::Compressed,
+            )?))
+        }
+        _ => Ok(Cow::Borrowed(vk_bytes)),
+    }
+}
+
+/// Reads the fields of a verifying key in order.
+struct VkReader<'a> {
+    bytes: &'a [u8],
+    encoding: PointEncoding,
+}
+
+impl VkReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], VerificationError> {
+        if self.bytes.len() < len {
+            return Err(VerificationError::MalformedVk(
+                "unexpected end of verifying key".to_string(),
+            ));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, VerificationError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn g1(&mut self, name: &str) -> Result<G1Affine, VerificationError> {
+        let encoding = self.encoding;
+        decode_g1(self.take(encoding.g1_len())?, encoding).map_err(|reason| {
+            VerificationError::InvalidVkPoint {
+                point: name.to_string(),
+                reason,
+            }
+        })
+    }
+
+    fn g2(&mut self, name: &str) -> Result<G2Affine, VerificationError> {
+        let encoding = self.encoding;
+        decode_g2(self.take(encoding.g2_len())?, encoding).map_err(|reason| {
+            VerificationError::InvalidVkPoint {
+                point: name.to_string(),
+                reason,
+            }
+        })
+    }
+}
+
+/// The length of a verifying key's fields in the given encoding, if its counts fit in `bytes`.
+fn vk_len(bytes: &[u8], encoding: PointEncoding) -> Option<usize> {
+    let read_u32 = |offset: usize| {
+        let word = bytes.get(offset..offset.checked_add(4)?)?;
+        Some(u32::from_be_bytes(word.try_into().unwrap()) as usize)
+    };
+
+    let mut offset = 3 * encoding.g1_len() + 3 * encoding.g2_len();
+    let num_k = read_u32(offset)?;
+    offset = offset.checked_add(4 + num_k.checked_mul(encoding.g1_len())?)?;
+    let num_committed = read_u32(offset)?;
+    offset += 4;
+    for _ in 0..num_committed {
+        // Each count read moves past at least 4 bytes, so this ends once past the key.
+        let len = read_u32(offset)?;
+        offset = offset.checked_add(4 + len.checked_mul(4)?)?;
+    }
+    offset.checked_add(2 * encoding.g2_len())
+}
+
+fn finite<P: AffineRepr>(point: P) -> Result<P, PointError> {
+    if point.is_zero() {
+        Err(PointError::Infinity)
+    } else {
+        Ok(point)
+    }
+}
+
+/// Check that a point built outside the decoders is finite, on the curve and in the prime order
+/// subgroup.
+fn valid<P: SWCurveConfig>(point: Affine<P>) -> Result<Affine<P>, PointError> {
+    let point = finite(point)?;
+    check_point(
+        point.is_on_curve(),
+        point.is_in_correct_subgroup_assuming_on_curve(),
+    )?;
+    Ok(point)
+}
+
+/// Decode a G1 point, `X || Y` if uncompressed.
+fn decode_g1(bytes: &[u8], encoding: PointEncoding) -> Result<G1Affine, PointError> {
+    if is_infinity(bytes, encoding)? {
+        return Ok(G1Affine::identity());
+    }
+    let point = match encoding {
+        PointEncoding::Compressed => {
+            let x = decode_fq(&without_flags(bytes))?;
+            G1Affine::get_point_from_x_unchecked(x, bytes[0] & FLAG_MASK == FLAG_COMPRESSED_LARGEST)
+                .ok_or(PointError::NotOnCurve)?
+        }
+        PointEncoding::Uncompressed => {
+            let (x, y) = bytes.split_at(32);
+            G1Affine::new_unchecked(decode_fq(x)?, decode_fq(y)?)
+        }
+    };
+    check_point(
+        point.is_on_curve(),
+        point.is_in_correct_subgroup_assuming_on_curve(),
+    )?;
+    Ok(point)
+}
+
+/// Decode a G2 point, `X.A1 || X.A0 || Y.A1 || Y.A0` if uncompressed.
+fn decode_g2(bytes: &[u8], encoding: PointEncoding) -> Result<G2Affine, PointError> {
+    if is_infinity(bytes, encoding)? {
+        return Ok(G2Affine::identity());
+    }
+    let point = match encoding {
+        PointEncoding::Compressed => {
+            let x = decode_fq2(&without_flags(bytes))?;
+            G2Affine::get_point_from_x_unchecked(x, bytes[0] & FLAG_MASK == FLAG_COMPRESSED_LARGEST)
+                .ok_or(PointError::NotOnCurve)?
+        }
+        PointEncoding::Uncompressed => {
+            let (x, y) = bytes.split_at(64);
+            G2Affine::new_unchecked(decode_fq2(x)?, decode_fq2(y)?)
+        }
+    };
+    check_point(
+        point.is_on_curve(),
+        point.is_in_correct_subgroup_assuming_on_curve(),
+    )?;
+    Ok(point)
+}
+
+/// Whether an encoded point is the point at infinity, checking that its flags are valid.
+///
+/// gnark marks the point at infinity with a flag and zeros. Uncompressed points are canonical
+/// coordinates, which leave the flag bits clear, so all zeros (as the EVM encodes the point at
+/// infinity) is accepted too, but the flags of compressed points are not.
+fn is_infinity(bytes: &[u8], encoding: PointEncoding) -> Result<bool, PointError> {
+    let flags = bytes[0] & FLAG_MASK;
+    let rest_zero = bytes[0] & !FLAG_MASK == 0 && bytes[1..].iter().all(|&byte| byte == 0);
+    match (encoding, flags) {
+        (PointEncoding::Compressed, FLAG_COMPRESSED_INFINITY)
+        | (PointEncoding::Uncompressed, FLAG_UNCOMPRESSED_INFINITY) => {
+            if rest_zero {
+                Ok(true)
+            } else {
+                Err(PointError::InvalidFlags)
+            }
+        }
+        (PointEncoding::Compressed, FLAG_COMPRESSED_SMALLEST | FLAG_COMPRESSED_LARGEST) => {
+            Ok(false)
+        }
+        (PointEncoding::Uncompressed, 0) => Ok(rest_zero),
+        _ => Err(PointError::InvalidFlags),
+    }
+}
+
+fn check_point(on_curve: bool, in_subgroup: bool) -> Result<(), PointError> {
+    if !on_curve {
+        Err(PointError::NotOnCurve)
+    } else if !in_subgroup {
+        Err(PointError::NotInSubgroup)
+    } else {
+        Ok(())
+    }
+}
+
+fn without_flags(bytes: &[u8]) -> Vec<u8> {
+    let mut bytes = bytes.to_vec();
+    bytes[0] &= !FLAG_MASK;
+    bytes
+}
+
+/// Decode an element of the quadratic extension field, imaginary part first.
+fn decode_fq2(bytes: &[u8]) -> Result<Fq2, PointError> {
+    let (c1, c0) = bytes.split_at(32);
+    Ok(Fq2::new(decode_fq(c0)?, decode_fq(c1)?))
+}
+
+/// Decode a big-endian base field element, rejecting values that are not below the modulus.
+fn decode_fq(bytes: &[u8]) -> Result<Fq, PointError> {
+    BigInteger256::try_from(BigUint::from_bytes_be(bytes))
+        .ok()
+        .and_then(Fq::from_bigint)
+        .ok_or(PointError::NonCanonicalCoordinate)
+}
+
+/// Encode a G1 point in the given encoding. The point at infinity is all zeros when
+/// uncompressed, as the EVM expects.
+fn encode_g1(point: &G1Affine, encoding: PointEncoding) -> Vec<u8> {
+    let Some((x, y)) = point.xy() else {
+        return infinity_bytes(encoding.g1_len(), encoding);
+    };
+    match encoding {
+        PointEncoding::Compressed => with_flags(encode_fq(x), compressed_flag(*y > -*y)),
+        PointEncoding::Uncompressed => [encode_fq(x), encode_fq(y)].concat(),
+    }
+}
+
+/// Encode a G2 point in the given encoding.
+fn encode_g2(point: &G2Affine, encoding: PointEncoding) -> Vec<u8> {
+    let Some((x, y)) = point.xy() else {
+        return infinity_bytes(encoding.g2_len(), encoding);
+    };
+    match encoding {
+        // Fq2 orders by the imaginary part first, like gnark's lexicographic order.
+        PointEncoding::Compressed => with_flags(encode_fq2(x), compressed_flag(*y > -*y)),
+        PointEncoding::Uncompressed => [encode_fq2(x), encode_fq2(y)].concat(),
+    }
+}
+
+fn infinity_bytes(len: usize, encoding: PointEncoding) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    if encoding == PointEncoding::Compressed {
+        bytes[0] = FLAG_COMPRESSED_INFINITY;
+    }
+    bytes
+}
+
+fn compressed_flag(largest: bool) -> u8 {
+    if largest {
+        FLAG_COMPRESSED_LARGEST
+    } else {
+        FLAG_COMPRESSED_SMALLEST
+    }
+}
+
+fn with_flags(mut bytes: Vec<u8>, flags: u8) -> Vec<u8> {
+    bytes[0] |= flags;
+    bytes
+}
+
+fn encode_fq2(element: &Fq2) -> Vec<u8> {
+    [encode_fq(&element.c1), encode_fq(&element.c0)].concat()
+}
+
+fn encode_fq(element: &Fq) -> Vec<u8> {
+    element.into_bigint().to_bytes_be()
+}
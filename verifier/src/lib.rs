@@ -0,0 +1,270 @@
+//! Local verification of SP1 Groth16 and PLONK proofs.
+//!
+//! Proofs are checked with the gnark BN254 verifier against the SP1 circuit's `groth16_vk.bin` or
+//! `plonk_vk.bin`, so no prover client (or prover-sized machine) is needed to validate them.
+//! [`PreparedGroth16Vk`] checks many Groth16 proofs against a key parsed once instead.
+
+mod error;
+mod evm;
+mod fibonacci;
+mod fixture;
+mod groth16;
+mod prepared;
+mod system;
+mod vk;
+
+pub use error::VerificationError;
+pub use evm::{
+    circuit_selector, evm_public_inputs, verifier_selector, verify_evm_proof, EvmProof,
+    GROTH16_EVM_PROOF_LEN, SELECTOR_LEN,
+};
+pub use fibonacci::{
+    check_exact_fibonacci_public_values, check_fibonacci_public_values, verify_exact_fibonacci,
+    verify_fibonacci, VerifiedExactFibonacci, VerifiedFibonacci,
+};
+pub use fixture::{
+    verify_exact_fixture, verify_fixture, ExactFibonacciValues, SP1FibonacciProofFixture,
+};
+pub use groth16::{
+    convert_groth16_proof, convert_groth16_vk, Groth16Proof, Groth16Vk, PointEncoding, PointError,
+    ProofPoint, G1_COMPRESSED_LEN, G1_UNCOMPRESSED_LEN, G2_COMPRESSED_LEN, G2_UNCOMPRESSED_LEN,
+};
+pub use prepared::{verify_batch, PreparedGroth16Vk};
+pub use system::ProofSystem;
+pub use vk::{circuit_vk, default_vk_path, load_vk, resolve_vk_path};
+#[cfg(feature = "embedded-vk")]
+pub use vk::{EMBEDDED_GROTH16_VK, EMBEDDED_GROTH16_VK_SHA256};
+
+pub use ark_bn254::{G1Affine, G2Affine};
+use ark_ff::PrimeField;
+pub use gnark_bn254_verifier::Fr;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1Proof, SP1ProofKind, SP1ProofWithPublicValues};
+use std::{fs::File, panic, path::Path, str::FromStr};
+
+/// Load a proof saved with [`SP1ProofWithPublicValues::save`].
+pub fn load_proof(path: impl AsRef<Path>) -> Result<SP1ProofWithPublicValues, VerificationError> {
+    let path = path.as_ref();
+    File::open(path)
+        .map_err(bincode::Error::from)
+        .and_then(bincode::deserialize_from)
+        .map_err(|source| VerificationError::ProofLoad {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Verify an SP1 Groth16 proof against the bytes of the circuit's `groth16_vk.bin`.
+///
+/// This only establishes that *some* SP1 program produced the public values; use
+/// [`verify_program_vkey`] to check which one.
+pub fn verify_groth16(
+    proof: &SP1ProofWithPublicValues,
+    vk_bytes: &[u8],
+) -> Result<(), VerificationError> {
+    verify_proof(proof, vk_bytes, ProofSystem::Groth16)
+}
+
+/// Verify an SP1 PLONK proof against the bytes of the circuit's `plonk_vk.bin`.
+///
+/// Like [`verify_groth16`], this does not check which program was proven.
+pub fn verify_plonk(
+    proof: &SP1ProofWithPublicValues,
+    vk_bytes: &[u8],
+) -> Result<(), VerificationError> {
+    verify_proof(proof, vk_bytes, ProofSystem::Plonk)
+}
+
+/// Verify an SP1 Groth16 proof against a circuit verifying key prepared with
+/// [`PreparedGroth16Vk::new`], without parsing the key again.
+pub fn verify_groth16_prepared(
+    proof: &SP1ProofWithPublicValues,
+    vk: &PreparedGroth16Vk,
+) -> Result<(), VerificationError> {
+    let (raw_proof, public_inputs) = sp1_proof_inputs(proof, ProofSystem::Groth16, vk.hash())?;
+    vk.verify(&Groth16Proof::decode(&raw_proof)?, &public_inputs)
+}
+
+/// Verify an SP1 proof of the given proof system against the bytes of the matching circuit
+/// verifying key.
+pub fn verify_proof(
+    proof: &SP1ProofWithPublicValues,
+    vk_bytes: &[u8],
+    system: ProofSystem,
+) -> Result<(), VerificationError> {
+    let vk_bytes = groth16::sp1_vk_bytes(vk_bytes, system)?;
+    let vk_hash: [u8; 32] = Sha256::digest(&vk_bytes).into();
+    let (raw_proof, public_inputs) = sp1_proof_inputs(proof, system, vk_hash)?;
+    verify_encoded_proof(&raw_proof, &vk_bytes, public_inputs, system)
+}
+
+/// Check that an SP1 proof is of the given proof system, for the circuit verifying key with the
+/// given hash, and commits to its public values. Returns the decoded proof and its public inputs.
+fn sp1_proof_inputs(
+    proof: &SP1ProofWithPublicValues,
+    system: ProofSystem,
+    vk_hash: [u8; 32],
+) -> Result<(Vec<u8>, [Fr; 2]), VerificationError> {
+    let actual = ProofSystem::of(&proof.proof)?;
+    if actual != system {
+        return Err(VerificationError::WrongProofSystem {
+            expected: system,
+            actual,
+        });
+    }
+    let (public_inputs, gnark_proof, circuit_vkey_hash) = bn254_proof_parts(&proof.proof)?;
+
+    // The mock prover fills in the public inputs but leaves the proof itself empty.
+    if gnark_proof.is_empty() {
+        return Err(VerificationError::MockProof);
+    }
+
+    // SP1 records the hash of the circuit's verifying key in every proof, so a key from a
+    // different circuit version is caught before the pairing check.
+    if vk_hash != circuit_vkey_hash {
+        return Err(VerificationError::VkMismatch {
+            expected: circuit_vkey_hash,
+            actual: vk_hash,
+        });
+    }
+
+    let raw_proof = hex::decode(gnark_proof)
+        .map_err(|err| VerificationError::MalformedProof(err.to_string()))?;
+
+    // The first public input is the program vkey hash, the second the committed values digest.
+    let vkey_hash = parse_public_input(0, &public_inputs[0])?;
+    let committed_values_digest = parse_public_input(1, &public_inputs[1])?;
+
+    // The digest is only meaningful if it commits to the public values shipped with the proof.
+    let public_values_hash = hash_public_values(proof.public_values.as_slice());
+    if committed_values_digest != public_values_hash {
+        return Err(VerificationError::PublicValuesMismatch {
+            committed: committed_values_digest,
+            actual: public_values_hash,
+        });
+    }
+
+    let public_inputs = [Fr::from(vkey_hash), Fr::from(committed_values_digest)];
+    Ok((raw_proof, public_inputs))
+}
+
+/// Verify an encoded proof against the two public inputs of an SP1 proof with the gnark verifier.
+fn verify_encoded_proof(
+    encoded_proof: &[u8],
+    vk_bytes: &[u8],
+    public_inputs: [Fr; 2],
+    system: ProofSystem,
+) -> Result<(), VerificationError> {
+    // Reject invalid points with the point that failed, rather than a failed pairing check, and
+    // hand the gnark verifier the uncompressed encoding it parses.
+    let encoded_proof = match system {
+        ProofSystem::Groth16 => {
+            Groth16Proof::decode(encoded_proof)?.to_bytes(PointEncoding::Uncompressed)
+        }
+        ProofSystem::Plonk => encoded_proof.to_vec(),
+    };
+
+    // The gnark verifier panics instead of erroring on points it cannot decode.
+    let verified = panic::catch_unwind(|| {
+        gnark_bn254_verifier::verify(
+            &encoded_proof,
+            vk_bytes,
+            &public_inputs,
+            system.proving_system(),
+        )
+    })
+    .map_err(|_| {
+        VerificationError::MalformedProof(
+            "failed to decode the proof or verifying key points".to_string(),
+        )
+    })?;
+
+    if verified {
+        Ok(())
+    } else {
+        Err(VerificationError::Rejected(system))
+    }
+}
+
+/// Check that a proof is for the program with the given vkey hash, as returned by
+/// [`HashableKey::bytes32`](sp1_sdk::HashableKey::bytes32).
+pub fn verify_program_vkey(
+    proof: &SP1ProofWithPublicValues,
+    program_vkey: &str,
+) -> Result<(), VerificationError> {
+    let (public_inputs, _, _) = bn254_proof_parts(&proof.proof)?;
+
+    let expected = parse_program_vkey(program_vkey)?;
+    let actual = parse_public_input(0, &public_inputs[0])?;
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(VerificationError::ProgramVkeyMismatch {
+            expected: format!("0x{:0>64}", expected.to_str_radix(16)),
+            actual: format!("0x{:0>64}", actual.to_str_radix(16)),
+        })
+    }
+}
+
+/// Hash public values the way SP1 commits to them: SHA-256 with the top 3 bits masked off, so
+/// the digest fits in a BN254 scalar.
+pub fn hash_public_values(public_values: &[u8]) -> BigUint {
+    let mut hash: [u8; 32] = Sha256::digest(public_values).into();
+    hash[0] &= 0b00011111;
+    BigUint::from_bytes_be(&hash)
+}
+
+/// The public inputs, hex encoded proof and circuit vkey hash of a PLONK or Groth16 proof.
+///
+/// The proof is the one gnark's loader parses. For PLONK that is the raw proof: its encoded proof
+/// is the Solidity verifier's encoding, which gnark cannot parse. A Groth16 proof's encoded proof
+/// starts with the points gnark parses.
+fn bn254_proof_parts(
+    proof: &SP1Proof,
+) -> Result<(&[String; 2], &str, [u8; 32]), VerificationError> {
+    match proof {
+        SP1Proof::Plonk(plonk_proof) => Ok((
+            &plonk_proof.public_inputs,
+            &plonk_proof.raw_proof,
+            plonk_proof.plonk_vkey_hash,
+        )),
+        SP1Proof::Groth16(groth16_proof) => Ok((
+            &groth16_proof.public_inputs,
+            &groth16_proof.encoded_proof,
+            groth16_proof.groth16_vkey_hash,
+        )),
+        other => Err(VerificationError::UnsupportedProof(SP1ProofKind::from(
+            other,
+        ))),
+    }
+}
+
+/// Parse a `0x`-prefixed 32 byte program vkey hash.
+fn parse_program_vkey(program_vkey: &str) -> Result<BigUint, VerificationError> {
+    hex::decode(program_vkey.trim_start_matches("0x"))
+        .ok()
+        .filter(|bytes| bytes.len() == 32)
+        .map(|bytes| BigUint::from_bytes_be(&bytes))
+        .ok_or_else(|| VerificationError::MalformedProgramVkey(program_vkey.to_string()))
+}
+
+/// Parse the decimal public input at `index`, which must be a canonical BN254 scalar.
+fn parse_public_input(index: usize, value: &str) -> Result<BigUint, VerificationError> {
+    let parsed = BigUint::from_str(value).map_err(|_| VerificationError::MalformedPublicInput {
+        index,
+        value: value.to_string(),
+    })?;
+    check_canonical(index, parsed)
+}
+
+/// Check that the public input at `index` is below the BN254 scalar field modulus. `Fr::from`
+/// reduces its argument, so otherwise `x + r` would be accepted in place of `x`.
+fn check_canonical(index: usize, value: BigUint) -> Result<BigUint, VerificationError> {
+    if value < BigUint::from(Fr::MODULUS) {
+        Ok(value)
+    } else {
+        Err(VerificationError::NonCanonicalPublicInput { index, value })
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{groth16, Groth16Proof, Groth16Vk, ProofSystem, VerificationError};
+use ark_bn254::{Bn254, G1Projective};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{UniformRand, Zero};
+use gnark_bn254_verifier::Fr;
+use sha2::{Digest, Sha256};
+
+type G1Prepared = <Bn254 as Pairing>::G1Prepared;
+type G2Prepared = <Bn254 as Pairing>::G2Prepared;
+
+/// A Groth16 verifying key parsed once, with the parts of the pairing check that do not depend on
+/// the proof precomputed, for verifying many proofs against the same key.
+///
+/// Only keys without Pedersen commitments are supported, like the SP1 circuit's. Proofs for keys
+/// with commitments carry a commitment and proof of knowledge that this pairing check does not
+/// cover, so such keys are rejected rather than checked partially.
+#[derive(Debug, Clone)]
+pub struct PreparedGroth16Vk {
+    vk: Groth16Vk,
+    /// The SHA-256 of the key as SP1 ships it, which SP1 records in every Groth16 proof.
+    hash: [u8; 32],
+    /// `e(alpha, beta)`, the right hand side of the pairing check.
+    alpha_g1_beta_g2: PairingOutput<Bn254>,
+    gamma_g2_neg: G2Prepared,
+    delta_g2_neg: G2Prepared,
+}
+
+impl PreparedGroth16Vk {
+    /// Parse and prepare a Groth16 verifying key in either point encoding, e.g. the bytes of
+    /// `groth16_vk.bin`.
+    pub fn new(vk_bytes: &[u8]) -> Result<Self, VerificationError> {
+        let sp1_vk_bytes = groth16::sp1_vk_bytes(vk_bytes, ProofSystem::Groth16)?;
+        let vk = Groth16Vk::decode(&sp1_vk_bytes)?;
+        if vk.k.is_empty() {
+            return Err(VerificationError::MalformedVk(
+                "no point for the constant public input term".to_string(),
+            ));
+        }
+        if !vk.public_and_commitment_committed.is_empty() {
+            return Err(VerificationError::MalformedVk(
+                "keys with commitments are not supported".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            hash: Sha256::digest(&sp1_vk_bytes).into(),
+            alpha_g1_beta_g2: Bn254::pairing(vk.alpha_g1, vk.beta_g2),
+            gamma_g2_neg: (-vk.gamma_g2).into(),
+            delta_g2_neg: (-vk.delta_g2).into(),
+            vk,
+        })
+    }
+
+    /// The decoded verifying key.
+    pub fn vk(&self) -> &Groth16Vk {
+        &self.vk
+    }
+
+    /// The SHA-256 of the key as SP1 ships it, compressed.
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// The number of public inputs proofs for the key have.
+    pub fn num_public_inputs(&self) -> usize {
+        self.vk.k.len() - 1
+    }
+
+    /// Check a decoded proof and its public inputs against the key:
+    /// `e(A, B) = e(alpha, beta) * e(L, gamma) * e(C, delta)`, with `L` the public inputs
+    /// combined over the key's `K` points.
+    pub fn verify(
+        &self,
+        proof: &Groth16Proof,
+        public_inputs: &[Fr],
+    ) -> Result<(), VerificationError> {
+        let public_input_term = self.public_input_term(public_inputs)?;
+        let miller_loop = Bn254::multi_miller_loop(
+            [
+                G1Prepared::from(proof.a()),
+                G1Prepared::from(public_input_term.into_affine()),
+                G1Prepared::from(proof.c()),
+            ],
+            [
+                G2Prepared::from(proof.b()),
+                self.gamma_g2_neg.clone(),
+                self.delta_g2_neg.clone(),
+            ],
+        );
+
+        match Bn254::final_exponentiation(miller_loop) {
+            Some(result) if result == self.alpha_g1_beta_g2 => Ok(()),
+            _ => Err(VerificationError::Rejected(ProofSystem::Groth16)),
+        }
+    }
+
+    /// `K[0] + sum(public_inputs[i] * K[i + 1])`.
+    fn public_input_term(&self, public_inputs: &[Fr]) -> Result<G1Projective, VerificationError> {
+        if public_inputs.len() != self.num_public_inputs() {
+            return Err(VerificationError::PublicInputCount {
+                expected: self.num_public_inputs(),
+                actual: public_inputs.len(),
+            });
+        }
+        Ok(public_inputs
+            .iter()
+            .zip(&self.vk.k[1..])
+            .fold(self.vk.k[0].into_group(), |term, (input, k)| {
+                term + *k * input
+            }))
+    }
+}
+
+/// Verify many proofs against the same key with a single multi-pairing.
+///
+/// Each proof's pairing check is raised to a random scalar `r_i` and the checks are multiplied, so
+/// the `gamma` and `delta` terms collapse into one pairing each:
+///
+/// ```text
+/// prod(e(r_i * A_i, B_i)) = e(alpha, beta)^sum(r_i)
+///                           * e(sum(r_i * L_i), gamma)
+///                           * e(sum(r_i * C_i), delta)
+/// ```
+///
+/// A batch with an invalid proof passes with negligible probability. If the batch fails, the proofs
+/// are checked one by one to report the first invalid one.
+pub fn verify_batch<I: AsRef<[Fr]>>(
+    vk: &PreparedGroth16Vk,
+    proofs: &[(Groth16Proof, I)],
+) -> Result<(), VerificationError> {
+    let rejected = |index, source| VerificationError::BatchProofRejected {
+        index,
+        source: Box::new(source),
+    };
+
+    let mut rng = rand::thread_rng();
+    let mut g1 = Vec::with_capacity(proofs.len() + 2);
+    let mut g2 = Vec::with_capacity(proofs.len() + 2);
+    let mut public_input_terms = G1Projective::zero();
+    let mut c_terms = G1Projective::zero();
+    let mut scalar_sum = Fr::zero();
+    for (index, (proof, public_inputs)) in proofs.iter().enumerate() {
+        let public_input_term = vk
+            .public_input_term(public_inputs.as_ref())
+            .map_err(|err| rejected(index, err))?;
+        let r = Fr::rand(&mut rng);
+        g1.push(G1Prepared::from((proof.a() * r).into_affine()));
+        g2.push(G2Prepared::from(proof.b()));
+        public_input_terms += public_input_term * r;
+        c_terms += proof.c() * r;
+        scalar_sum += r;
+    }
+    g1.push(G1Prepared::from(public_input_terms.into_affine()));
+    g2.push(vk.gamma_g2_neg.clone());
+    g1.push(G1Prepared::from(c_terms.into_affine()));
+    g2.push(vk.delta_g2_neg.clone());
+
+    let result = Bn254::final_exponentiation(Bn254::multi_miller_loop(g1, g2));
+    if result == Some(vk.alpha_g1_beta_g2 * scalar_sum) {
+        return Ok(());
+    }
+
+    for (index, (proof, public_inputs)) in proofs.iter().enumerate() {
+        vk.verify(proof, public_inputs.as_ref())
+            .map_err(|err| rejected(index, err))?;
+    }
+    // A batch of valid proofs always passes, so one of the proofs was rejected above.
+    Ok(())
+}
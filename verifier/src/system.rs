@@ -0,0 +1,49 @@
+use crate::VerificationError;
+use gnark_bn254_verifier::ProvingSystem;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{SP1Proof, SP1ProofKind};
+
+/// The EVM-compatible proof systems SP1 can wrap a proof into.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ProofSystem {
+    Plonk,
+    Groth16,
+}
+
+impl ProofSystem {
+    /// The proof system of an SP1 proof, if it is one that can be verified locally.
+    pub fn of(proof: &SP1Proof) -> Result<Self, VerificationError> {
+        match proof {
+            SP1Proof::Plonk(_) => Ok(Self::Plonk),
+            SP1Proof::Groth16(_) => Ok(Self::Groth16),
+            other => Err(VerificationError::UnsupportedProof(SP1ProofKind::from(
+                other,
+            ))),
+        }
+    }
+
+    /// The file name of the verifying key inside an SP1 circuit artifacts directory.
+    pub fn vk_file(self) -> &'static str {
+        match self {
+            Self::Plonk => "plonk_vk.bin",
+            Self::Groth16 => "groth16_vk.bin",
+        }
+    }
+
+    /// The environment variable conventionally pointing at the verifying key.
+    pub fn vk_env_var(self) -> &'static str {
+        match self {
+            Self::Plonk => "SP1_PLONK_VK",
+            Self::Groth16 => "SP1_GROTH16_VK",
+        }
+    }
+
+    pub(crate) fn proving_system(self) -> ProvingSystem {
+        match self {
+            Self::Plonk => ProvingSystem::Plonk,
+            Self::Groth16 => ProvingSystem::Groth16,
+        }
+    }
+}
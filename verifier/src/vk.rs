@@ -0,0 +1,84 @@
+use crate::{ProofSystem, VerificationError};
+use sp1_sdk::SP1_CIRCUIT_VERSION;
+use std::path::{Path, PathBuf};
+
+/// The Groth16 verifying key embedded at build time, checked in under `verifier/circuits`.
+#[cfg(feature = "embedded-vk")]
+pub const EMBEDDED_GROTH16_VK: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/groth16_vk.bin"));
+
+/// The SHA-256 of [`EMBEDDED_GROTH16_VK`], which SP1 records in every Groth16 proof as
+/// `groth16_vkey_hash`. The build fails unless it is the hash pinned in `groth16_vk.pin`.
+#[cfg(feature = "embedded-vk")]
+pub const EMBEDDED_GROTH16_VK_SHA256: [u8; 32] =
+    *include_bytes!(concat!(env!("OUT_DIR"), "/groth16_vk.sha256"));
+
+// The embedded key is only valid for the circuit version it was pinned for.
+#[cfg(feature = "embedded-vk")]
+const _: () = {
+    let pinned = env!("SP1_PINNED_CIRCUIT_VERSION").as_bytes();
+    let sdk = SP1_CIRCUIT_VERSION.as_bytes();
+    let mut matches = pinned.len() == sdk.len();
+    let mut i = 0;
+    while matches && i < pinned.len() {
+        matches = pinned[i] == sdk[i];
+        i += 1;
+    }
+    assert!(
+        matches,
+        "groth16_vk.pin is for another circuit version than sp1-sdk, pin the new Groth16 key"
+    );
+};
+
+/// The default location of a verifying key for the circuit version of the linked SP1 SDK, e.g.
+/// `~/.sp1/circuits/<version>/groth16_vk.bin`.
+pub fn default_vk_path(system: ProofSystem) -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        home.join(".sp1")
+            .join("circuits")
+            .join(SP1_CIRCUIT_VERSION)
+            .join(system.vk_file())
+    })
+}
+
+/// Find a verifying key, preferring an explicitly configured path over the default circuit
+/// artifacts location.
+pub fn resolve_vk_path(
+    system: ProofSystem,
+    explicit: Option<&Path>,
+) -> Result<PathBuf, VerificationError> {
+    let candidates: Vec<PathBuf> = match explicit {
+        Some(path) => vec![path.to_path_buf()],
+        None => default_vk_path(system).into_iter().collect(),
+    };
+
+    match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(VerificationError::VkNotFound {
+            system,
+            tried: candidates,
+        }),
+    }
+}
+
+/// Read a verifying key (e.g. `groth16_vk.bin`) from disk.
+pub fn load_vk(path: impl AsRef<Path>) -> Result<Vec<u8>, VerificationError> {
+    let path = path.as_ref();
+    std::fs::read(path).map_err(|source| VerificationError::VkLoad {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Load a verifying key from `explicit` if given, and otherwise from the embedded key (for
+/// Groth16 with the `embedded-vk` feature) or the default circuit artifacts location.
+pub fn circuit_vk(
+    system: ProofSystem,
+    explicit: Option<&Path>,
+) -> Result<Vec<u8>, VerificationError> {
+    #[cfg(feature = "embedded-vk")]
+    if system == ProofSystem::Groth16 && explicit.is_none() {
+        return Ok(EMBEDDED_GROTH16_VK.to_vec());
+    }
+
+    resolve_vk_path(system, explicit).and_then(load_vk)
+}
@@ -0,0 +1,678 @@
+//! Regression tests against the golden Groth16 fixtures in `tests/fixtures`, which are generated by
+//! the `golden_fixtures` example.
+
+use alloy_sol_types::SolType;
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, Field, PrimeField};
+use fibonacci_lib::PublicValuesStruct;
+use fibonacci_verifier::{
+    circuit_selector, convert_groth16_proof, convert_groth16_vk, evm_public_inputs, load_proof,
+    load_vk, verify_batch, verify_evm_proof, verify_exact_fixture, verify_fibonacci,
+    verify_fixture, verify_groth16, verify_groth16_prepared, verify_proof, Fr, Groth16Proof,
+    Groth16Vk, PointEncoding, PointError, PreparedGroth16Vk, ProofPoint, ProofSystem,
+    SP1FibonacciProofFixture, VerificationError, VerifiedFibonacci,
+};
+use gnark_bn254_verifier::ProvingSystem;
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, PlonkBn254Proof, ProverClient, SP1Proof, SP1ProofWithPublicValues};
+use std::{path::PathBuf, str::FromStr};
+
+/// The ELF of the Fibonacci program the golden proofs are for.
+const FIBONACCI_ELF: &[u8] = include_bytes!("../../elf/riscv32im-succinct-zkvm-elf");
+
+/// The vkey of the Fibonacci program the golden proofs are for.
+const PROGRAM_VKEY: &str = "0x0094911ed0ab604bda4bfbd4ea6cb46a30743b91f0d0a208d0891dd4bc9613ce";
+
+/// The BN254 scalar field modulus.
+const FR_MODULUS: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+/// The values the golden proofs commit to.
+const VERIFIED: VerifiedFibonacci = VerifiedFibonacci {
+    n: 10,
+    a: 55,
+    b: 89,
+};
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn golden_vk() -> Vec<u8> {
+    load_vk(fixture_path("groth16_vk.bin")).unwrap()
+}
+
+fn golden_proof() -> SP1ProofWithPublicValues {
+    load_proof(fixture_path("groth16-proof.bin")).unwrap()
+}
+
+fn golden_fixture() -> SP1FibonacciProofFixture {
+    SP1FibonacciProofFixture::load(fixture_path("groth16-fixture.json")).unwrap()
+}
+
+/// The onchain proof bytes and public values of the golden fixture.
+fn golden_evm_proof() -> (Vec<u8>, Vec<u8>) {
+    let fixture = golden_fixture();
+    (
+        hex::decode(fixture.proof.trim_start_matches("0x")).unwrap(),
+        hex::decode(fixture.public_values.trim_start_matches("0x")).unwrap(),
+    )
+}
+
+/// The ABI encoded public values of the Fibonacci program for `n`.
+fn public_values_of(n: u32) -> Vec<u8> {
+    let (a, b) = fibonacci_lib::fibonacci(n);
+    PublicValuesStruct::abi_encode(&PublicValuesStruct { n, a, b })
+}
+
+/// Apply `f` to the Groth16 part of a proof.
+fn tamper(proof: &mut SP1ProofWithPublicValues, f: impl FnOnce(&mut [String; 2], &mut Vec<u8>)) {
+    let SP1Proof::Groth16(groth16_proof) = &mut proof.proof else {
+        panic!("golden proof is not a Groth16 proof");
+    };
+    let mut encoded_proof = hex::decode(&groth16_proof.encoded_proof).unwrap();
+    f(&mut groth16_proof.public_inputs, &mut encoded_proof);
+    groth16_proof.encoded_proof = hex::encode(encoded_proof);
+}
+
+#[test]
+fn golden_proofs_are_for_the_committed_elf() {
+    // Rebuilding the ELF changes the program vkey, so the golden fixtures must be regenerated
+    // along with it.
+    let (_, vk) = ProverClient::mock().setup(FIBONACCI_ELF);
+    assert_eq!(vk.bytes32(), PROGRAM_VKEY);
+}
+
+#[test]
+fn verifies_golden_proof() {
+    verify_groth16(&golden_proof(), &golden_vk()).unwrap();
+}
+
+#[test]
+fn verifies_golden_proof_of_fibonacci() {
+    let verified = verify_fibonacci(
+        &golden_proof(),
+        &golden_vk(),
+        ProofSystem::Groth16,
+        PROGRAM_VKEY,
+    )
+    .unwrap();
+    assert_eq!(verified, VERIFIED);
+}
+
+#[test]
+fn verifies_golden_fixture() {
+    let verified = verify_fixture(
+        &golden_fixture(),
+        &golden_vk(),
+        ProofSystem::Groth16,
+        PROGRAM_VKEY,
+    )
+    .unwrap();
+    assert_eq!(verified, VERIFIED);
+}
+
+#[test]
+fn verifies_golden_evm_proof() {
+    let (proof_bytes, public_values) = golden_evm_proof();
+    verify_evm_proof(
+        &proof_bytes,
+        &public_values,
+        PROGRAM_VKEY,
+        &golden_vk(),
+        ProofSystem::Groth16,
+    )
+    .unwrap();
+}
+
+#[test]
+fn golden_fixture_selector_identifies_its_vk() {
+    let selector = golden_fixture().selector().unwrap();
+    assert_eq!(
+        selector,
+        circuit_selector(&golden_vk(), ProofSystem::Groth16).unwrap()
+    );
+    let other_vk = load_vk(fixture_path("other_groth16_vk.bin")).unwrap();
+    assert_ne!(
+        selector,
+        circuit_selector(&other_vk, ProofSystem::Groth16).unwrap()
+    );
+}
+
+#[test]
+fn verifies_golden_proof_compressed() {
+    let mut proof = golden_proof();
+    tamper(&mut proof, |_, encoded_proof| {
+        *encoded_proof = convert_groth16_proof(encoded_proof, PointEncoding::Compressed).unwrap();
+    });
+    verify_groth16(&proof, &golden_vk()).unwrap();
+}
+
+#[test]
+fn verifies_against_uncompressed_vk() {
+    let vk = convert_groth16_vk(&golden_vk(), PointEncoding::Uncompressed).unwrap();
+    assert_eq!(
+        PointEncoding::of_vk(&vk).unwrap(),
+        PointEncoding::Uncompressed
+    );
+    verify_groth16(&golden_proof(), &vk).unwrap();
+    // The selector is of the key as SP1 ships it, compressed.
+    verify_fixture(&golden_fixture(), &vk, ProofSystem::Groth16, PROGRAM_VKEY).unwrap();
+}
+
+#[test]
+fn converts_between_encodings_losslessly() {
+    let vk = golden_vk();
+    let uncompressed_vk = convert_groth16_vk(&vk, PointEncoding::Uncompressed).unwrap();
+    assert_eq!(
+        convert_groth16_vk(&uncompressed_vk, PointEncoding::Compressed).unwrap(),
+        vk
+    );
+
+    let (proof_bytes, _) = golden_evm_proof();
+    let encoded_proof = &proof_bytes[4..];
+    let compressed_proof = convert_groth16_proof(encoded_proof, PointEncoding::Compressed).unwrap();
+    assert_eq!(compressed_proof.len(), 128);
+    assert_eq!(
+        convert_groth16_proof(&compressed_proof, PointEncoding::Uncompressed).unwrap(),
+        encoded_proof
+    );
+}
+
+#[test]
+fn rejects_explicit_encoding_mismatch() {
+    let (proof_bytes, _) = golden_evm_proof();
+    assert!(matches!(
+        Groth16Proof::decode_with(&proof_bytes[4..], PointEncoding::Compressed),
+        Err(VerificationError::MalformedProof(_))
+    ));
+    assert!(Groth16Vk::decode_with(&golden_vk(), PointEncoding::Uncompressed).is_err());
+}
+
+#[test]
+fn verifies_golden_proof_against_prepared_vk() {
+    let vk = PreparedGroth16Vk::new(&golden_vk()).unwrap();
+    assert_eq!(vk.num_public_inputs(), 2);
+    verify_groth16_prepared(&golden_proof(), &vk).unwrap();
+
+    // A key prepared from either encoding is the same key, with the same hash.
+    let uncompressed_vk = convert_groth16_vk(&golden_vk(), PointEncoding::Uncompressed).unwrap();
+    let other = PreparedGroth16Vk::new(&uncompressed_vk).unwrap();
+    assert_eq!((other.vk(), other.hash()), (vk.vk(), vk.hash()));
+}
+
+#[test]
+fn verifies_decoded_proof_against_prepared_vk() {
+    let vk = PreparedGroth16Vk::new(&golden_vk()).unwrap();
+    let (proof_bytes, public_values) = golden_evm_proof();
+    let proof = Groth16Proof::decode(&proof_bytes[4..]).unwrap();
+    let public_inputs = evm_public_inputs(PROGRAM_VKEY, &public_values).unwrap();
+    vk.verify(&proof, &public_inputs).unwrap();
+
+    let other_inputs = evm_public_inputs(PROGRAM_VKEY, &public_values_of(11)).unwrap();
+    assert!(matches!(
+        vk.verify(&proof, &other_inputs),
+        Err(VerificationError::Rejected(ProofSystem::Groth16))
+    ));
+    assert!(matches!(
+        vk.verify(&proof, &public_inputs[..1]),
+        Err(VerificationError::PublicInputCount {
+            expected: 2,
+            actual: 1
+        })
+    ));
+}
+
+#[test]
+fn prepared_vk_agrees_with_gnark() {
+    let vk_bytes = golden_vk();
+    let vk = PreparedGroth16Vk::new(&vk_bytes).unwrap();
+    let (proof_bytes, public_values) = golden_evm_proof();
+    let public_inputs = evm_public_inputs(PROGRAM_VKEY, &public_values).unwrap();
+    let other_inputs = evm_public_inputs(PROGRAM_VKEY, &public_values_of(11)).unwrap();
+
+    let proof = Groth16Proof::decode(&proof_bytes[4..]).unwrap();
+    let mut swapped_bytes = proof_bytes[4..].to_vec();
+    let (a, rest) = swapped_bytes.split_at_mut(64);
+    a.swap_with_slice(&mut rest[128..]);
+    let swapped = Groth16Proof::decode(&swapped_bytes).unwrap();
+
+    let cases = [
+        (proof, public_inputs, true),
+        (rerandomize(&proof, &vk, 0), public_inputs, true),
+        (proof, other_inputs, false),
+        (proof, [public_inputs[1], public_inputs[0]], false),
+        (swapped, public_inputs, false),
+        (rerandomize(&swapped, &vk, 1), public_inputs, false),
+    ];
+    for (index, (proof, public_inputs, valid)) in cases.iter().enumerate() {
+        let gnark = gnark_bn254_verifier::verify(
+            &proof.to_bytes(PointEncoding::Uncompressed),
+            &vk_bytes,
+            public_inputs,
+            ProvingSystem::Groth16,
+        );
+        let prepared = vk.verify(proof, public_inputs).is_ok();
+        assert_eq!((prepared, gnark), (*valid, *valid), "case {index}");
+    }
+}
+
+#[test]
+fn prepared_vk_rejects_keys_with_commitments() {
+    let mut vk = Groth16Vk::decode(&golden_vk()).unwrap();
+    vk.public_and_commitment_committed = vec![vec![1]];
+    assert!(matches!(
+        PreparedGroth16Vk::new(&vk.to_bytes(PointEncoding::Compressed)),
+        Err(VerificationError::MalformedVk(_))
+    ));
+}
+
+#[test]
+fn rejects_flipped_proof_byte() {
+    let vk = golden_vk();
+    for index in [0, 63, 64, 191, 192, 255] {
+        let mut proof = golden_proof();
+        tamper(&mut proof, |_, encoded_proof| encoded_proof[index] ^= 1);
+        assert!(
+            verify_groth16(&proof, &vk).is_err(),
+            "proof with byte {index} flipped was accepted"
+        );
+    }
+}
+
+#[test]
+fn rejects_flipped_evm_proof_byte() {
+    let vk = golden_vk();
+    let (proof_bytes, public_values) = golden_evm_proof();
+    for index in [0, 4, 100, proof_bytes.len() - 1] {
+        let mut proof_bytes = proof_bytes.clone();
+        proof_bytes[index] ^= 1;
+        assert!(
+            verify_evm_proof(
+                &proof_bytes,
+                &public_values,
+                PROGRAM_VKEY,
+                &vk,
+                ProofSystem::Groth16
+            )
+            .is_err(),
+            "proof with byte {index} flipped was accepted"
+        );
+    }
+}
+
+#[test]
+fn rejects_swapped_proof_points() {
+    // A and C are both valid G1 points, so only the pairing check can catch this.
+    let mut proof = golden_proof();
+    tamper(&mut proof, |_, encoded_proof| {
+        let (a, rest) = encoded_proof.split_at_mut(64);
+        a.swap_with_slice(&mut rest[128..]);
+    });
+    assert!(matches!(
+        verify_groth16(&proof, &golden_vk()),
+        Err(VerificationError::Rejected(ProofSystem::Groth16))
+    ));
+}
+
+/// Re-randomize a Groth16 proof into another valid proof of the same statement:
+/// `A' = A / r`, `B' = r * B + r * s * delta` and `C' = C + s * A`.
+fn rerandomize(proof: &Groth16Proof, vk: &PreparedGroth16Vk, seed: u64) -> Groth16Proof {
+    let r = Fr::from(seed + 2);
+    let s = Fr::from(seed + 3);
+    Groth16Proof::new(
+        (proof.a() * r.inverse().unwrap()).into_affine(),
+        (proof.b() * r + vk.vk().delta_g2 * (r * s)).into_affine(),
+        (proof.c() + proof.a() * s).into_affine(),
+    )
+    .unwrap()
+}
+
+/// The golden proof and re-randomized copies of it, with their public inputs.
+fn golden_batch(vk: &PreparedGroth16Vk, len: u64) -> Vec<(Groth16Proof, [Fr; 2])> {
+    let (proof_bytes, public_values) = golden_evm_proof();
+    let proof = Groth16Proof::decode(&proof_bytes[4..]).unwrap();
+    let public_inputs = evm_public_inputs(PROGRAM_VKEY, &public_values).unwrap();
+    (0..len)
+        .map(|seed| (rerandomize(&proof, vk, seed), public_inputs))
+        .collect()
+}
+
+/// Assert that verifying `proof` fails on `point`, for `reason`.
+fn assert_invalid_point(
+    proof: &SP1ProofWithPublicValues,
+    expected_point: ProofPoint,
+    expected_reason: PointError,
+) {
+    match verify_groth16(proof, &golden_vk()) {
+        Err(VerificationError::InvalidProofPoint { point, reason }) => {
+            assert_eq!((point, reason), (expected_point, expected_reason))
+        }
+        other => panic!("expected {expected_point} to be rejected, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_proof_points_at_infinity() {
+    for (point, range) in [
+        (ProofPoint::A, 0..64),
+        (ProofPoint::B, 64..192),
+        (ProofPoint::C, 192..256),
+    ] {
+        let mut proof = golden_proof();
+        tamper(&mut proof, |_, encoded_proof| encoded_proof[range].fill(0));
+        assert_invalid_point(&proof, point, PointError::Infinity);
+    }
+}
+
+#[test]
+fn rejects_proof_points_off_curve() {
+    // Flipping the lowest bit of a y coordinate keeps it in the field, but moves it off the curve.
+    for (point, index) in [
+        (ProofPoint::A, 63),
+        (ProofPoint::B, 191),
+        (ProofPoint::C, 255),
+    ] {
+        let mut proof = golden_proof();
+        tamper(&mut proof, |_, encoded_proof| encoded_proof[index] ^= 1);
+        assert_invalid_point(&proof, point, PointError::NotOnCurve);
+    }
+}
+
+#[test]
+fn rejects_non_canonical_proof_coordinate() {
+    let mut proof = golden_proof();
+    // Above the modulus, but with the flag bits clear.
+    tamper(&mut proof, |_, encoded_proof| {
+        encoded_proof[..32].fill(0x3f)
+    });
+    assert_invalid_point(&proof, ProofPoint::A, PointError::NonCanonicalCoordinate);
+}
+
+#[test]
+fn rejects_compressed_flags_on_uncompressed_points() {
+    // Zero coordinates with the flags of a compressed point are not the point at infinity.
+    for flags in [0b10 << 6, 0b11 << 6] {
+        let mut proof = golden_proof();
+        tamper(&mut proof, |_, encoded_proof| {
+            encoded_proof[..64].fill(0);
+            encoded_proof[0] = flags;
+        });
+        assert_invalid_point(&proof, ProofPoint::A, PointError::InvalidFlags);
+    }
+}
+
+#[test]
+fn rejects_proof_point_outside_subgroup() {
+    // G2 has a large cofactor, so most points on the curve are outside the prime order subgroup.
+    let b = (1u64..)
+        .filter_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), false))
+        .find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    let mut proof = golden_proof();
+    tamper(&mut proof, |_, encoded_proof| {
+        let words = [b.x.c1, b.x.c0, b.y.c1, b.y.c0]
+            .map(|coordinate| coordinate.into_bigint().to_bytes_be())
+            .concat();
+        encoded_proof[64..192].copy_from_slice(&words);
+    });
+    assert_invalid_point(&proof, ProofPoint::B, PointError::NotInSubgroup);
+}
+
+#[test]
+fn rejects_invalid_points_of_constructed_proofs() {
+    let (proof_bytes, _) = golden_evm_proof();
+    let proof = Groth16Proof::decode(&proof_bytes[4..]).unwrap();
+    let (a, b, c) = (proof.a(), proof.b(), proof.c());
+    assert_eq!(Groth16Proof::new(a, b, c).unwrap(), proof);
+
+    let off_curve = G1Affine::new_unchecked(Fq::from(1u64), Fq::from(1u64));
+    let outside_subgroup = (1u64..)
+        .filter_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), false))
+        .find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    for (result, expected_point, expected_reason) in [
+        (
+            Groth16Proof::new(G1Affine::identity(), b, c),
+            ProofPoint::A,
+            PointError::Infinity,
+        ),
+        (
+            Groth16Proof::new(a, outside_subgroup, c),
+            ProofPoint::B,
+            PointError::NotInSubgroup,
+        ),
+        (
+            Groth16Proof::new(a, b, off_curve),
+            ProofPoint::C,
+            PointError::NotOnCurve,
+        ),
+    ] {
+        match result {
+            Err(VerificationError::InvalidProofPoint { point, reason }) => {
+                assert_eq!((point, reason), (expected_point, expected_reason))
+            }
+            other => panic!("expected {expected_point} to be rejected, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn rejects_swapped_public_inputs() {
+    let mut proof = golden_proof();
+    tamper(&mut proof, |public_inputs, _| public_inputs.swap(0, 1));
+    assert!(verify_groth16(&proof, &golden_vk()).is_err());
+}
+
+#[test]
+fn rejects_non_canonical_public_inputs() {
+    // Each input plus the modulus reduces to the same scalar, but must not verify as it.
+    let vk = golden_vk();
+    for index in 0..2 {
+        let mut proof = golden_proof();
+        tamper(&mut proof, |public_inputs, _| {
+            let value = BigUint::from_str(&public_inputs[index]).unwrap();
+            public_inputs[index] = (value + BigUint::from_str(FR_MODULUS).unwrap()).to_string();
+        });
+        assert!(
+            matches!(
+                verify_groth16(&proof, &vk),
+                Err(VerificationError::NonCanonicalPublicInput { index: i, .. }) if i == index
+            ),
+            "public input {index} plus the modulus was not rejected"
+        );
+    }
+}
+
+#[test]
+fn rejects_other_public_values() {
+    // Public values claiming a different, but correct, Fibonacci number.
+    let (proof_bytes, _) = golden_evm_proof();
+    let other_public_values = public_values_of(11);
+    assert!(matches!(
+        verify_evm_proof(
+            &proof_bytes,
+            &other_public_values,
+            PROGRAM_VKEY,
+            &golden_vk(),
+            ProofSystem::Groth16
+        ),
+        Err(VerificationError::Rejected(ProofSystem::Groth16))
+    ));
+}
+
+#[test]
+fn rejects_other_program_vkey() {
+    let other_vkey = "0x00000000000000000000000000000000000000000000000000000000000000ff";
+    let (proof_bytes, public_values) = golden_evm_proof();
+    assert!(matches!(
+        verify_evm_proof(
+            &proof_bytes,
+            &public_values,
+            other_vkey,
+            &golden_vk(),
+            ProofSystem::Groth16
+        ),
+        Err(VerificationError::Rejected(ProofSystem::Groth16))
+    ));
+    assert!(matches!(
+        verify_fibonacci(
+            &golden_proof(),
+            &golden_vk(),
+            ProofSystem::Groth16,
+            other_vkey
+        ),
+        Err(VerificationError::ProgramVkeyMismatch { .. })
+    ));
+}
+
+#[test]
+fn rejects_wrong_vk() {
+    let other_vk = load_vk(fixture_path("other_groth16_vk.bin")).unwrap();
+    assert!(matches!(
+        verify_groth16(&golden_proof(), &other_vk),
+        Err(VerificationError::VkMismatch { .. })
+    ));
+    assert!(matches!(
+        verify_groth16_prepared(&golden_proof(), &PreparedGroth16Vk::new(&other_vk).unwrap()),
+        Err(VerificationError::VkMismatch { .. })
+    ));
+    assert!(matches!(
+        verify_fixture(
+            &golden_fixture(),
+            &other_vk,
+            ProofSystem::Groth16,
+            PROGRAM_VKEY
+        ),
+        Err(VerificationError::SelectorMismatch { .. })
+    ));
+}
+
+#[test]
+fn rejects_groth16_proof_as_plonk() {
+    assert!(matches!(
+        verify_fibonacci(
+            &golden_proof(),
+            &golden_vk(),
+            ProofSystem::Plonk,
+            PROGRAM_VKEY
+        ),
+        Err(VerificationError::WrongProofSystem { .. })
+    ));
+}
+
+/// The golden proof relabelled as a PLONK proof for `vk_bytes`, with the given raw proof.
+fn plonk_proof(vk_bytes: &[u8], raw_proof: &[u8]) -> SP1ProofWithPublicValues {
+    let mut proof = golden_proof();
+    let SP1Proof::Groth16(groth16_proof) = proof.proof else {
+        panic!("golden proof is not a Groth16 proof");
+    };
+    proof.proof = SP1Proof::Plonk(PlonkBn254Proof {
+        public_inputs: groth16_proof.public_inputs,
+        encoded_proof: groth16_proof.encoded_proof,
+        raw_proof: hex::encode(raw_proof),
+        plonk_vkey_hash: Sha256::digest(vk_bytes).into(),
+    });
+    proof
+}
+
+#[test]
+fn rejects_plonk_proof_without_raw_proof() {
+    // The encoded proof is the Solidity verifier's encoding; only the raw proof is handed to
+    // gnark, so a proof without one is a placeholder however long its encoded proof is.
+    let vk = golden_vk();
+    assert!(matches!(
+        verify_proof(&plonk_proof(&vk, &[]), &vk, ProofSystem::Plonk),
+        Err(VerificationError::MockProof)
+    ));
+}
+
+#[test]
+fn rejects_malformed_plonk_raw_proof() {
+    let vk = golden_vk();
+    assert!(matches!(
+        verify_proof(&plonk_proof(&vk, &[0; 64]), &vk, ProofSystem::Plonk),
+        Err(VerificationError::MalformedProof(_))
+    ));
+}
+
+#[test]
+fn rejects_onchain_plonk_proof() {
+    let vk = golden_vk();
+    let (_, public_values) = golden_evm_proof();
+    let mut proof_bytes = circuit_selector(&vk, ProofSystem::Plonk).unwrap().to_vec();
+    proof_bytes.extend([0; 768]);
+
+    assert!(matches!(
+        verify_evm_proof(
+            &proof_bytes,
+            &public_values,
+            PROGRAM_VKEY,
+            &vk,
+            ProofSystem::Plonk
+        ),
+        Err(VerificationError::OnchainPlonkProof)
+    ));
+}
+
+#[test]
+fn rejects_golden_fixture_as_exact() {
+    assert!(matches!(
+        verify_exact_fixture(
+            &golden_fixture(),
+            &golden_vk(),
+            ProofSystem::Groth16,
+            PROGRAM_VKEY
+        ),
+        Err(VerificationError::FixtureModeMismatch {
+            expected_exact: true
+        })
+    ));
+}
+
+#[test]
+fn verifies_batch_of_proofs() {
+    let vk = PreparedGroth16Vk::new(&golden_vk()).unwrap();
+    let batch = golden_batch(&vk, 8);
+    for (proof, public_inputs) in &batch {
+        vk.verify(proof, public_inputs).unwrap();
+    }
+    verify_batch(&vk, &batch).unwrap();
+    verify_batch::<[Fr; 2]>(&vk, &[]).unwrap();
+}
+
+#[test]
+fn rejects_batch_with_invalid_proof() {
+    let vk = PreparedGroth16Vk::new(&golden_vk()).unwrap();
+    let mut batch = golden_batch(&vk, 8);
+    batch[3].1 = evm_public_inputs(PROGRAM_VKEY, &public_values_of(11)).unwrap();
+    match verify_batch(&vk, &batch) {
+        Err(VerificationError::BatchProofRejected { index, source }) => {
+            assert_eq!(index, 3);
+            assert!(matches!(
+                *source,
+                VerificationError::Rejected(ProofSystem::Groth16)
+            ));
+        }
+        other => panic!("expected proof 3 to be rejected, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_batch_with_wrong_public_input_count() {
+    let vk = PreparedGroth16Vk::new(&golden_vk()).unwrap();
+    let mut batch: Vec<(Groth16Proof, Vec<Fr>)> = golden_batch(&vk, 3)
+        .into_iter()
+        .map(|(proof, public_inputs)| (proof, public_inputs.to_vec()))
+        .collect();
+    batch[1].1.pop();
+    assert!(matches!(
+        verify_batch(&vk, &batch),
+        Err(VerificationError::BatchProofRejected { index: 1, source })
+            if matches!(*source, VerificationError::PublicInputCount { .. })
+    ));
+}
@@ -0,0 +1,139 @@
+//! Tests against Groth16 artifacts produced by SP1's gnark prover, rather than the arkworks
+//! stand-in circuit of the golden fixtures.
+//!
+//! `sp1_example_groth16_vk.bin` and `sp1_example_groth16_proof.json` are the example artifacts
+//! shipped in `sp1-prover`'s `scripts/artifacts` for v3.0.0-rc1: a gnark Groth16 key in the same
+//! serialization as the circuit's `groth16_vk.bin`, and a proof for it with the public inputs
+//! `[0, 0]`.
+
+use fibonacci_verifier::{
+    load_proof, verify_groth16, Fr, Groth16Proof, Groth16Vk, PointEncoding, PreparedGroth16Vk,
+    ProofSystem, VerificationError, G1_UNCOMPRESSED_LEN, G2_UNCOMPRESSED_LEN,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+use std::path::PathBuf;
+
+/// The SHA-256 of `sp1_example_groth16_vk.bin`, which SP1 would record as `groth16_vkey_hash` in
+/// proofs for the key.
+const EXAMPLE_VK_SHA256: &str = "c0219ad41553229ff0cac198033b306f50314b00d3d2f68551a420dc6f3840d4";
+
+/// The length of the uncompressed proof points at the start of a gnark encoded proof.
+const PROOF_POINTS_LEN: usize = 2 * G1_UNCOMPRESSED_LEN + G2_UNCOMPRESSED_LEN;
+
+#[derive(Deserialize)]
+struct ExampleProof {
+    public_inputs: Vec<String>,
+    encoded_proof: String,
+}
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn example_vk() -> Vec<u8> {
+    std::fs::read(fixture_path("sp1_example_groth16_vk.bin")).unwrap()
+}
+
+fn load_example_proof() -> ExampleProof {
+    let file = std::fs::File::open(fixture_path("sp1_example_groth16_proof.json")).unwrap();
+    serde_json::from_reader(file).unwrap()
+}
+
+/// The example proof's points and public inputs.
+fn example_proof() -> (Vec<u8>, Vec<Fr>) {
+    let proof = load_example_proof();
+    let encoded_proof = hex::decode(proof.encoded_proof).unwrap();
+    let public_inputs = proof
+        .public_inputs
+        .iter()
+        .map(|input| input.parse().unwrap())
+        .collect();
+    (encoded_proof[..PROOF_POINTS_LEN].to_vec(), public_inputs)
+}
+
+/// The example proof as an SP1 Groth16 proof, recording the example key's hash the way SP1 does.
+/// The public values are those of the golden proof, which the example does not commit to.
+fn example_sp1_proof() -> SP1ProofWithPublicValues {
+    let example = load_example_proof();
+    let mut proof = load_proof(fixture_path("groth16-proof.bin")).unwrap();
+    let SP1Proof::Groth16(groth16_proof) = &mut proof.proof else {
+        panic!("golden proof is not a Groth16 proof");
+    };
+    groth16_proof.public_inputs = example.public_inputs.try_into().unwrap();
+    groth16_proof.encoded_proof = example.encoded_proof;
+    groth16_proof.groth16_vkey_hash = hex::decode(EXAMPLE_VK_SHA256).unwrap().try_into().unwrap();
+    proof
+}
+
+#[test]
+fn decodes_example_vk() {
+    let vk = Groth16Vk::decode(&example_vk()).unwrap();
+    assert_eq!(vk.k.len(), 3);
+    assert!(vk.public_and_commitment_committed.is_empty());
+}
+
+#[test]
+fn reencodes_example_vk_to_the_same_bytes() {
+    let bytes = example_vk();
+    let vk = Groth16Vk::decode(&bytes).unwrap();
+    assert_eq!(vk.to_bytes(PointEncoding::Compressed), bytes);
+
+    let uncompressed = vk.to_bytes(PointEncoding::Uncompressed);
+    assert_eq!(Groth16Vk::decode(&uncompressed).unwrap(), vk);
+}
+
+#[test]
+fn prepares_example_vk_with_its_sp1_hash() {
+    let bytes = example_vk();
+    let vk = PreparedGroth16Vk::new(&bytes).unwrap();
+    assert_eq!(vk.num_public_inputs(), 2);
+    assert_eq!(hex::encode(vk.hash()), EXAMPLE_VK_SHA256);
+    assert_eq!(vk.hash(), <[u8; 32]>::from(Sha256::digest(&bytes)));
+}
+
+#[test]
+fn verifies_example_proof() {
+    let vk = PreparedGroth16Vk::new(&example_vk()).unwrap();
+    let (proof_bytes, public_inputs) = example_proof();
+    let proof = Groth16Proof::decode(&proof_bytes).unwrap();
+    vk.verify(&proof, &public_inputs).unwrap();
+}
+
+#[test]
+fn rejects_tampered_example_proof() {
+    // The example circuit does not constrain its public inputs, so only the proof can be
+    // tampered with. A and C are both valid G1 points, so swapping them is only caught by the
+    // pairing check.
+    let vk = PreparedGroth16Vk::new(&example_vk()).unwrap();
+    let (mut proof_bytes, public_inputs) = example_proof();
+    let (a, rest) = proof_bytes.split_at_mut(G1_UNCOMPRESSED_LEN);
+    a.swap_with_slice(&mut rest[G2_UNCOMPRESSED_LEN..]);
+    let proof = Groth16Proof::decode(&proof_bytes).unwrap();
+
+    assert!(matches!(
+        vk.verify(&proof, &public_inputs),
+        Err(VerificationError::Rejected(ProofSystem::Groth16))
+    ));
+}
+
+#[test]
+fn checks_the_groth16_vkey_hash_sp1_records() {
+    // The proof records the example key's hash, so against the example key it gets past the hash
+    // check, only to fail on the public values the example does not commit to.
+    let proof = example_sp1_proof();
+    assert!(matches!(
+        verify_groth16(&proof, &example_vk()),
+        Err(VerificationError::PublicValuesMismatch { .. })
+    ));
+
+    let golden_vk = std::fs::read(fixture_path("groth16_vk.bin")).unwrap();
+    assert!(matches!(
+        verify_groth16(&proof, &golden_vk),
+        Err(VerificationError::VkMismatch { expected, .. }) if hex::encode(expected) == EXAMPLE_VK_SHA256
+    ));
+}